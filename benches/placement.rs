@@ -0,0 +1,30 @@
+// To compare against an earlier version, run this on the earlier commit with
+// "cargo bench --bench placement -- --save-baseline before", then on the later one with
+// "cargo bench --bench placement -- --baseline before".
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use word_games::*;
+use word_games::word_search::{CandidateSearch, Puzzle, Settings};
+
+fn bench_find_best_puzzle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_best_puzzle");
+    group.sample_size(10);
+    let lists = [("WORDS_1", slice_str_to_strings(&word_list::WORDS_1)), ("ALL_SECOND_GRADE", slice_str_to_strings(&word_list::ALL_SECOND_GRADE))];
+    for (list_name, words) in lists.iter() {
+        for (search_name, candidate_search) in [("full_scan", CandidateSearch::FullScan), ("intersection_first", CandidateSearch::IntersectionFirst)].iter() {
+            let settings = Settings {
+                candidate_search: candidate_search.clone(),
+                try_count_max: 1,
+                ..Default::default()
+            };
+            group.bench_with_input(BenchmarkId::new(*search_name, list_name), words, |b, words| {
+                random::set_seed(1);
+                b.iter(|| Puzzle::find_best_puzzle(words, &settings).unwrap());
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_find_best_puzzle);
+criterion_main!(benches);
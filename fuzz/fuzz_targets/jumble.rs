@@ -0,0 +1,35 @@
+#![no_main]
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+use word_games::jumble::puzzle::{Difficulty, Mode, Puzzle, Settings};
+use word_games::random;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    phrases: Vec<String>,
+    blank_count: u8,
+    blank_percent: Option<u8>,
+    difficulty: u8,
+    scramble: bool,
+    seed: u64,
+}
+
+fuzz_target!(|input: Input| {
+    let phrases = input.phrases.iter().map(|phrase| phrase.as_str()).collect::<Vec<_>>();
+    let mut settings = Settings::default();
+    settings.blank_count = input.blank_count as usize;
+    settings.blank_percent = input.blank_percent.map(|percent| percent as usize);
+    settings.difficulty = match input.difficulty % 3 {
+        0 => Difficulty::Easy,
+        1 => Difficulty::Medium,
+        _ => Difficulty::Hard,
+    };
+    settings.mode = if input.scramble { Mode::Scramble } else { Mode::Blanks };
+    random::set_seed(input.seed);
+    if let Ok(mut puzzle) = Puzzle::new(&phrases, &settings) {
+        if puzzle.build().is_ok() {
+            puzzle.get_string(true);
+            puzzle.get_answer_string();
+        }
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use word_games::word_list::{lint_list, FileFormat, LintSettings, WordList};
+
+fuzz_target!(|text: &str| {
+    for format in [FileFormat::Text, FileFormat::Csv, FileFormat::Json].iter() {
+        if let Ok(list) = WordList::parse("fuzz", text, *format) {
+            for out_format in [FileFormat::Text, FileFormat::Csv, FileFormat::Json].iter() {
+                list.to_format_string(*out_format);
+            }
+            lint_list(&list, &LintSettings::default());
+        }
+    }
+});
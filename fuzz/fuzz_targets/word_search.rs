@@ -0,0 +1,52 @@
+#![no_main]
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+use word_games::grid::DIRECTIONS;
+use word_games::random;
+use word_games::word_search::{CandidateSearch, ExcelStyle, Puzzle, Ranking, Settings};
+
+// Enough words to fill the grid without making each run slow.
+const WORD_COUNT_MAX: usize = 20;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    words: Vec<String>,
+    directions: Vec<u8>,
+    expansion: f32,
+    full_scan: bool,
+    rank_by_intersections: bool,
+    forbid_contained_words: bool,
+    prevent_same_axis_overlap: bool,
+    seed: u64,
+}
+
+fuzz_target!(|input: Input| {
+    let mut settings = Settings::default();
+    settings.directions = input.directions.iter().map(|index| DIRECTIONS[*index as usize % DIRECTIONS.len()]).collect();
+    settings.expansion = input.expansion;
+    settings.candidate_search = if input.full_scan { CandidateSearch::FullScan } else { CandidateSearch::IntersectionFirst };
+    settings.ranking = if input.rank_by_intersections { Ranking::Intersections } else { Ranking::Compact };
+    settings.forbid_contained_words = input.forbid_contained_words;
+    settings.prevent_same_axis_overlap = input.prevent_same_axis_overlap;
+    settings.try_count_max = 1;
+    let words = input.words.into_iter().take(WORD_COUNT_MAX).collect::<Vec<_>>();
+    random::set_seed(input.seed);
+    if let Ok(mut puzzle) = Puzzle::find_best_puzzle(&words, &settings) {
+        for (word, placement) in puzzle.get_placements().iter() {
+            let positions = placement.get_positions(word.chars().count());
+            assert_eq!(positions.iter().map(|position| puzzle.get_char(position)).collect::<String>(), *word);
+            assert!(puzzle.find_word_at(positions.first().unwrap(), positions.last().unwrap()).is_some());
+        }
+        if settings.forbid_contained_words {
+            assert!(puzzle.find_ambiguous_words().is_empty());
+        }
+        puzzle.get_puzzle_string();
+        if puzzle.get_excel_string(ExcelStyle::Density).is_ok() && settings.forbid_contained_words {
+            assert!(puzzle.find_ambiguous_words().is_empty());
+        }
+        for word in puzzle.get_words().clone().iter() {
+            assert!(puzzle.count_occurrences(word) >= 1);
+        }
+        puzzle.find_ambiguous_words();
+    }
+});
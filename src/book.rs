@@ -0,0 +1,161 @@
+use crate::*;
+use crate::random::rng;
+use crate::word_list::WordList;
+
+use rand::seq::SliceRandom;
+
+const SECTION_LINE: &str = "=============================================================================";
+const PUZZLE_LINE: &str = "-----------------------------------------------------------------------------";
+
+#[derive(Clone, Debug)]
+pub enum PuzzleKind {
+    WordSearch(word_search::Settings),
+    Jumble(jumble::puzzle::Settings),
+}
+
+pub struct Book {
+    title: String,
+    pages: Vec<Page>,
+}
+
+struct Page {
+    number: usize,
+    title: String,
+    puzzle: String,
+    answer: String,
+}
+
+impl Book {
+    /// Create one puzzle per group. Each group is a title, such as a theme, and the words for that
+    /// puzzle.
+    pub fn generate(title: &str, groups: &[(String, Vec<String>)], kind: &PuzzleKind) -> Result<Self, String> {
+        if groups.is_empty() {
+            return Err("A book needs at least one group of words.".to_string());
+        }
+        let mut pages = vec![];
+        for (index, (group_title, words)) in groups.iter().enumerate() {
+            if words.is_empty() {
+                return Err(format!("The group \"{}\" has no words.", group_title));
+            }
+            let (puzzle, answer) = match kind {
+                PuzzleKind::WordSearch(settings) => {
+                    let mut puzzle = word_search::Puzzle::find_best_puzzle(words, settings)?;
+                    let answer = puzzle.get_puzzle_string();
+                    puzzle.random_fill_optional()?;
+                    (format!("{}\n{}", puzzle.get_puzzle_string(), puzzle.get_word_bank_string()), answer)
+                },
+                PuzzleKind::Jumble(settings) => {
+                    let phrases = words.iter().map(|word| word.as_str()).collect::<Vec<_>>();
+                    let mut puzzle = jumble::puzzle::Puzzle::new(&phrases, settings)?;
+                    puzzle.build()?;
+                    (puzzle.get_string(false), puzzle.get_answer_string())
+                },
+            };
+            pages.push(Page {
+                number: index + 1,
+                title: group_title.clone(),
+                puzzle,
+                answer,
+            });
+        }
+        Ok(Self {
+            title: title.to_string(),
+            pages,
+        })
+    }
+
+    pub fn get_string(&self) -> String {
+        let mut s = format!("{}\n{}\n\n", self.title, SECTION_LINE);
+        s.push_str("Contents\n\n");
+        for page in self.pages.iter() {
+            s.push_str(&format!("{:>4}. {}\n", page.number, page.title));
+        }
+        s.push_str(&format!("{:>4}  Answer Key\n", ""));
+        for page in self.pages.iter() {
+            s.push_str(&format!("\n{}\n\nPuzzle {}: {}\n\n{}", PUZZLE_LINE, page.number, page.title, page.puzzle));
+        }
+        s.push_str(&format!("\n{}\n\nAnswer Key\n", SECTION_LINE));
+        for page in self.pages.iter() {
+            s.push_str(&format!("\nPuzzle {}: {}\n\n{}", page.number, page.title, page.answer));
+        }
+        s
+    }
+}
+
+/// Shuffle the words and deal them into group_count groups whose sizes differ by at most one.
+pub fn partition_random(words: &[String], group_count: usize) -> Result<Vec<(String, Vec<String>)>, String> {
+    check_group_count(words, group_count)?;
+    let mut words = words.to_vec();
+    words.shuffle(&mut rng());
+    let mut groups = vec![vec![]; group_count];
+    for (index, word) in words.into_iter().enumerate() {
+        groups[index % group_count].push(word);
+    }
+    Ok(groups.into_iter()
+        .enumerate()
+        .map(|(index, words)| (format!("Mixed Words {}", index + 1), words))
+        .collect())
+}
+
+/// Sort the words from shortest to longest and cut them into group_count runs, so that early
+/// puzzles use short words and later ones get harder.
+pub fn partition_by_length(words: &[String], group_count: usize) -> Result<Vec<(String, Vec<String>)>, String> {
+    check_group_count(words, group_count)?;
+    let mut words = words.to_vec();
+    words.shuffle(&mut rng());
+    words.sort_by_key(|word| word.chars().count());
+    let base_size = words.len() / group_count;
+    let extra = words.len() % group_count;
+    let mut groups = vec![];
+    for index in 0..group_count {
+        let group_size = base_size + if index < extra { 1 } else { 0 };
+        let group = words.drain(..group_size).collect::<Vec<_>>();
+        let length_min = group.iter().map(|word| word.chars().count()).min().unwrap();
+        let length_max = group.iter().map(|word| word.chars().count()).max().unwrap();
+        let title = if length_min == length_max {
+            format!("{}-Letter Words", length_min)
+        } else {
+            format!("{} to {} Letter Words", length_min, length_max)
+        };
+        groups.push((title, group));
+    }
+    Ok(groups)
+}
+
+/// One group per tag, for themed puzzles. When there are more tags than group_count, the tags with
+/// the most words are used. A word with several tags can appear in more than one puzzle.
+pub fn partition_by_tag(list: &WordList, group_count: usize) -> Result<Vec<(String, Vec<String>)>, String> {
+    let mut groups = list.get_tags().iter()
+        .map(|tag| (tag.clone(), list.filter_by_tag(tag).get_words()))
+        .collect::<Vec<_>>();
+    if groups.is_empty() {
+        return Err("None of the words have tags.".to_string());
+    }
+    if group_count == 0 || group_count > groups.len() {
+        return Err(format!("Can't make {} puzzles from {} tags.", group_count, groups.len()));
+    }
+    groups.sort_by(|a, b| a.1.len().cmp(&b.1.len()).reverse());
+    groups.truncate(group_count);
+    Ok(groups)
+}
+
+fn check_group_count(words: &[String], group_count: usize) -> Result<(), String> {
+    if group_count == 0 {
+        Err("The number of puzzles must be at least one.".to_string())
+    } else if group_count > words.len() {
+        Err(format!("Can't make {} puzzles from only {} words.", group_count, words.len()))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn main() {
+    let words = slice_str_to_strings(&word_list::ALL_SECOND_GRADE);
+    let groups = partition_random(&words, 20).unwrap();
+    let settings = word_search::Settings {
+        directions: vec![word_search::Direction::E, word_search::Direction::SE, word_search::Direction::S],
+        ..Default::default()
+    };
+    let book = Book::generate("Second Grade Word Searches", &groups, &PuzzleKind::WordSearch(settings)).unwrap();
+    println!("{}", book.get_string());
+}
@@ -0,0 +1,562 @@
+use crate::*;
+use crate::word_list::{FileFormat, LengthBand, LintSettings, SampleSettings, Severity, WordList};
+use crate::word_search::{CandidateSearch, Direction, ExcelStyle, Ranking, Settings};
+
+use clap::{App, AppSettings, Arg, ArgMatches, ErrorKind, SubCommand};
+use std::ffi::OsString;
+
+const DEFAULT_LIST: &str = "WORDS_1";
+
+pub fn run<I, T>(args: I) -> Result<(), String>
+    where I: IntoIterator<Item = T>,
+          T: Into<OsString> + Clone,
+{
+    let matches = match create_app().get_matches_from_safe(args) {
+        Ok(matches) => matches,
+        Err(e) if e.kind == ErrorKind::HelpDisplayed || e.kind == ErrorKind::VersionDisplayed => {
+            println!("{}", e.message);
+            return Ok(());
+        },
+        Err(e) => return Err(e.message),
+    };
+    match matches.subcommand() {
+        ("wordsearch", Some(matches)) => run_word_search(matches),
+        ("jumble", Some(matches)) => run_jumble(matches),
+        ("cloze", Some(matches)) => run_cloze(matches),
+        ("book", Some(matches)) => run_book(matches),
+        ("wordlist", Some(matches)) => match matches.subcommand() {
+            ("sample", Some(matches)) => run_word_list_sample(matches),
+            ("convert", Some(matches)) => run_word_list_convert(matches),
+            ("lint", Some(matches)) => run_word_list_lint(matches),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn create_app() -> App<'static, 'static> {
+    let list_args = || vec![
+        Arg::with_name("list")
+            .long("list")
+            .takes_value(true)
+            .conflicts_with("file")
+            .help("Name of a built-in word list such as WORDS_1 or ALL_SECOND_GRADE"),
+        Arg::with_name("file")
+            .long("file")
+            .short("f")
+            .takes_value(true)
+            .help("Word list file (.txt with one word or phrase per line, .csv or .json)"),
+        Arg::with_name("tag")
+            .long("tag")
+            .takes_value(true)
+            .help("Use only the words with this tag"),
+        Arg::with_name("grade")
+            .long("grade")
+            .takes_value(true)
+            .help("Use only the words for this grade level"),
+    ];
+    let common_args = || vec![
+        Arg::with_name("seed")
+            .long("seed")
+            .takes_value(true)
+            .help("Seed for the random number generator so that output can be reproduced"),
+        Arg::with_name("output")
+            .long("output")
+            .short("o")
+            .takes_value(true)
+            .help("Write to this file instead of standard output"),
+    ];
+    let word_search_args = || vec![
+        Arg::with_name("directions")
+            .long("directions")
+            .short("d")
+            .takes_value(true)
+            .help("Comma-separated directions such as E,SE,S, or \"all\""),
+        Arg::with_name("expansion")
+            .long("expansion")
+            .short("x")
+            .takes_value(true)
+            .help("From 0.0 (as compact as possible) to 1.0 (as loose as possible)"),
+        Arg::with_name("tries")
+            .long("tries")
+            .short("t")
+            .takes_value(true)
+            .help("Number of puzzles to generate before keeping the best by --ranking"),
+        Arg::with_name("forbid-contained")
+            .long("forbid-contained")
+            .help("Keep each word from lying inside another word or appearing in more than one place"),
+        Arg::with_name("seconds")
+            .long("seconds")
+            .takes_value(true)
+            .help("Stop trying new puzzles after this many seconds"),
+        Arg::with_name("search")
+            .long("search")
+            .takes_value(true)
+            .possible_values(&["full", "intersection"])
+            .help("Try every position for each word, or start with positions that cross other words"),
+        Arg::with_name("ranking")
+            .long("ranking")
+            .takes_value(true)
+            .possible_values(&["compact", "intersections"])
+            .help("Favor compact puzzles or puzzles with many crossing words"),
+        Arg::with_name("allow-same-axis")
+            .long("allow-same-axis")
+            .help("Let words overlap while running along the same line"),
+    ];
+    let jumble_args = || vec![
+        Arg::with_name("blanks")
+            .long("blanks")
+            .short("b")
+            .takes_value(true)
+            .help("Number of letters to blank out of each phrase"),
+        Arg::with_name("blank-percent")
+            .long("blank-percent")
+            .takes_value(true)
+            .conflicts_with("blanks")
+            .help("Percentage of the letters to blank out of each word, at least two per phrase"),
+        Arg::with_name("difficulty")
+            .long("difficulty")
+            .takes_value(true)
+            .possible_values(&["easy", "medium", "hard"])
+            .help("Favor blanking vowels and never first letters (easy), or consonant clusters and letters that could be other letters (hard)"),
+        Arg::with_name("mode")
+            .long("mode")
+            .takes_value(true)
+            .possible_values(&["blanks", "scramble"])
+            .help("Blank out some letters, or scramble the letters of each word"),
+        Arg::with_name("dictionary")
+            .long("dictionary")
+            .takes_value(true)
+            .help("Word list file of known words to add to the built-in lists, for checking scrambles and answers"),
+        Arg::with_name("unique")
+            .long("unique")
+            .requires("dictionary")
+            .help("Only blank letters where the missing letters fit back in just one way that makes words in --dictionary"),
+        Arg::with_name("shared-bank")
+            .long("shared-bank")
+            .help("Pool the missing letters of every phrase into one letter bank"),
+    ];
+    App::new("word_games")
+        .about("Generates word search and jumble puzzles")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(SubCommand::with_name("wordsearch")
+            .about("Generate a word search puzzle")
+            .args(&list_args())
+            .args(&common_args())
+            .args(&word_search_args())
+            .arg(Arg::with_name("format")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["text", "excel-reveal", "excel-hint", "excel-density"])
+                .default_value("text"))
+            .arg(Arg::with_name("answers")
+                .long("answers")
+                .help("With text output, also print the grid before it's filled with random letters"))
+            .arg(Arg::with_name("clues")
+                .long("clues")
+                .help("With text output, list definitions instead of the words where the list has them"))
+            .arg(Arg::with_name("play")
+                .long("play")
+                .conflicts_with_all(&["format", "output", "answers"])
+                .help("Play the puzzle in the terminal instead of printing it")))
+        .subcommand(SubCommand::with_name("jumble")
+            .about("Generate a jumble puzzle")
+            .args(&list_args())
+            .args(&common_args())
+            .args(&jumble_args())
+            .arg(Arg::with_name("format")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["text", "html"])
+                .default_value("text")
+                .help("Plain text, or an HTML worksheet with boxes to fill in and an answer key page"))
+            .arg(Arg::with_name("title")
+                .long("title")
+                .takes_value(true)
+                .default_value("Jumble")
+                .help("Heading for the HTML worksheet"))
+            .arg(Arg::with_name("answers")
+                .long("answers")
+                .help("Show each phrase above its jumble"))
+            .arg(Arg::with_name("phrases")
+                .long("phrases")
+                .takes_value(true)
+                .conflicts_with_all(&["list", "file", "tag", "grade", "riddle"])
+                .help("Phrase file (.txt, .csv or .json) with optional columns hint, category and blanks"))
+            .arg(Arg::with_name("riddle")
+                .long("riddle")
+                .takes_value(true)
+                .requires("riddle-answer")
+                .help("Make a classic jumble whose circled letters spell the answer to this question"))
+            .arg(Arg::with_name("riddle-answer")
+                .long("riddle-answer")
+                .takes_value(true)
+                .requires("riddle")
+                .help("The answer to the riddle"))
+            .arg(Arg::with_name("riddle-words")
+                .long("riddle-words")
+                .takes_value(true)
+                .requires("riddle")
+                .help("Number of words to scramble for the riddle [default: 4]")))
+        .subcommand(SubCommand::with_name("cloze")
+            .about("Generate a fill-in-the-blank worksheet from the example sentences in a word list")
+            .args(&list_args())
+            .args(&common_args())
+            .arg(Arg::with_name("letters")
+                .long("letters")
+                .takes_value(true)
+                .help("Blank only this many letters of each word instead of the whole word"))
+            .arg(Arg::with_name("answers")
+                .long("answers")
+                .help("Show each sentence below its blanked version")))
+        .subcommand(SubCommand::with_name("book")
+            .about("Generate a numbered book of puzzles with a table of contents and an answer key")
+            .args(&list_args())
+            .args(&common_args())
+            .args(&word_search_args())
+            .args(&jumble_args())
+            .arg(Arg::with_name("kind")
+                .long("kind")
+                .short("k")
+                .takes_value(true)
+                .possible_values(&["wordsearch", "jumble"])
+                .default_value("wordsearch"))
+            .arg(Arg::with_name("count")
+                .long("count")
+                .short("n")
+                .takes_value(true)
+                .required(true)
+                .help("Number of puzzles in the book"))
+            .arg(Arg::with_name("partition")
+                .long("partition")
+                .takes_value(true)
+                .possible_values(&["random", "length", "tag"])
+                .default_value("random")
+                .help("How to split the word list among the puzzles"))
+            .arg(Arg::with_name("title")
+                .long("title")
+                .takes_value(true)
+                .default_value("Puzzle Book")))
+        .subcommand(SubCommand::with_name("wordlist")
+            .about("Work with word lists")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(SubCommand::with_name("sample")
+                .about("Pick a random sublist of words")
+                .args(&list_args())
+                .args(&common_args())
+                .arg(Arg::with_name("count")
+                    .long("count")
+                    .short("n")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Number of words to pick"))
+                .arg(Arg::with_name("lengths")
+                    .long("lengths")
+                    .takes_value(true)
+                    .help("Words to take from each range of lengths, such as 3-5:4,6-8:3"))
+                .arg(Arg::with_name("shared-letters")
+                    .long("shared-letters")
+                    .takes_value(true)
+                    .help("Each word must share at least this many letters with another chosen word"))
+                .arg(Arg::with_name("exclude")
+                    .long("exclude")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .help("Word list file of words to leave out, such as those already used"))
+                .arg(Arg::with_name("format")
+                    .long("format")
+                    .takes_value(true)
+                    .possible_values(&["text", "csv", "json", "rust"])
+                    .default_value("text")))
+            .subcommand(SubCommand::with_name("convert")
+                .about("Clean up a word list and write it in another format")
+                .args(&list_args())
+                .arg(Arg::with_name("output")
+                    .long("output")
+                    .short("o")
+                    .takes_value(true)
+                    .required(true)
+                    .help("File to write, with the format taken from its extension")))
+            .subcommand(SubCommand::with_name("lint")
+                .about("Check a word list for duplicates, ambiguous words and words that won't fit")
+                .args(&list_args())
+                .arg(Arg::with_name("length-max")
+                    .long("length-max")
+                    .takes_value(true)
+                    .help("Longest word allowed, usually the grid size"))
+                .arg(Arg::with_name("allow-spaces")
+                    .long("allow-spaces")
+                    .help("Allow phrases with spaces, as for jumbles"))))
+}
+
+fn run_word_search(matches: &ArgMatches) -> Result<(), String> {
+    apply_seed(matches)?;
+    let list = get_word_list(matches)?;
+    let settings = get_word_search_settings(matches)?;
+    let mut puzzle = word_search::Puzzle::find_best_puzzle(&list.get_words(), &settings)?;
+    if matches.is_present("play") {
+        return word_search::play::Play::new(puzzle)?.run().map_err(|e| e.to_string());
+    }
+    let output = match matches.value_of("format").unwrap() {
+        "excel-reveal" => puzzle.get_excel_string(ExcelStyle::Reveal)?,
+        "excel-hint" => puzzle.get_excel_string(ExcelStyle::Hint)?,
+        "excel-density" => puzzle.get_excel_string(ExcelStyle::Density)?,
+        _ => {
+            let answers = if matches.is_present("answers") { puzzle.get_puzzle_string() } else { "".to_string() };
+            puzzle.random_fill_optional()?;
+            let word_bank = if matches.is_present("clues") { list.get_clue_string() } else { puzzle.get_word_bank_string() };
+            let mut s = format!("{}\n{}", puzzle.get_puzzle_string(), word_bank);
+            if !answers.is_empty() {
+                s.push_str(&format!("\nAnswers:\n\n{}", answers));
+            }
+            s
+        },
+    };
+    write_output(matches, &output)
+}
+
+fn get_word_search_settings(matches: &ArgMatches) -> Result<Settings, String> {
+    let mut settings = Settings::default();
+    if let Some(expansion) = parse_optional::<f32>(matches, "expansion")? {
+        if !(0.0..=1.0).contains(&expansion) {
+            return Err(format!("Expansion must be from 0.0 to 1.0, not {}.", expansion));
+        }
+        settings.expansion = expansion;
+    }
+    if let Some(try_count_max) = parse_optional::<usize>(matches, "tries")? {
+        settings.try_count_max = try_count_max.max(1);
+    }
+    if let Some(directions) = matches.value_of("directions") {
+        settings.directions = parse_directions(directions)?;
+    }
+    settings.create_seconds_max = parse_optional::<u64>(matches, "seconds")?;
+    match matches.value_of("search") {
+        Some("full") => settings.candidate_search = CandidateSearch::FullScan,
+        Some("intersection") => settings.candidate_search = CandidateSearch::IntersectionFirst,
+        _ => {},
+    }
+    match matches.value_of("ranking") {
+        Some("compact") => settings.ranking = Ranking::Compact,
+        Some("intersections") => settings.ranking = Ranking::Intersections,
+        _ => {},
+    }
+    settings.forbid_contained_words = matches.is_present("forbid-contained");
+    settings.prevent_same_axis_overlap = !matches.is_present("allow-same-axis");
+    Ok(settings)
+}
+
+fn get_jumble_settings(matches: &ArgMatches) -> Result<jumble::puzzle::Settings, String> {
+    let mut settings = jumble::puzzle::Settings::default();
+    if let Some(blank_count) = parse_optional::<usize>(matches, "blanks")? {
+        settings.blank_count = blank_count;
+    }
+    settings.blank_percent = parse_optional::<usize>(matches, "blank-percent")?;
+    match matches.value_of("difficulty") {
+        Some("easy") => settings.difficulty = jumble::puzzle::Difficulty::Easy,
+        Some("hard") => settings.difficulty = jumble::puzzle::Difficulty::Hard,
+        _ => {},
+    }
+    if matches.value_of("mode") == Some("scramble") {
+        settings.mode = jumble::puzzle::Mode::Scramble;
+    }
+    settings.require_unique_solution = matches.is_present("unique");
+    settings.shared_letter_bank = matches.is_present("shared-bank");
+    if let Some(path) = matches.value_of("dictionary") {
+        let dictionary = jumble::dictionary::Dictionary::read_file(path).map_err(|e| e.to_string())?;
+        settings.dictionary.extend(&dictionary.get_words().iter().collect::<Vec<_>>());
+    } else if settings.mode == jumble::puzzle::Mode::Scramble || matches.is_present("riddle") {
+        eprintln!("Without --dictionary, scrambles are only checked against the few words in the built-in lists and may spell other words.");
+    }
+    Ok(settings)
+}
+
+fn run_jumble(matches: &ArgMatches) -> Result<(), String> {
+    apply_seed(matches)?;
+    let phrases = get_words(matches)?;
+    let phrases = phrases.iter().map(|phrase| phrase.as_str()).collect::<Vec<_>>();
+    let mut settings = get_jumble_settings(matches)?;
+    if let Some(riddle_word_count) = parse_optional::<usize>(matches, "riddle-words")? {
+        settings.riddle_word_count = riddle_word_count;
+    }
+    let mut puzzle = match (matches.value_of("riddle"), matches.value_of("phrases")) {
+        (Some(question), _) => {
+            let riddle = jumble::riddle::Riddle::new(question, matches.value_of("riddle-answer").unwrap())?;
+            jumble::puzzle::Puzzle::new_classic(&riddle, &phrases, &settings)?
+        },
+        (None, Some(path)) => {
+            let list = jumble::phrase_list::PhraseList::read_file(path).map_err(|e| e.to_string())?;
+            jumble::puzzle::Puzzle::from_phrase_list(&list, &settings)?
+        },
+        (None, None) => jumble::puzzle::Puzzle::new(&phrases, &settings)?,
+    };
+    puzzle.build()?;
+    let output = match matches.value_of("format").unwrap() {
+        "html" => jumble::worksheet::get_html_string(&puzzle, matches.value_of("title").unwrap()),
+        _ => puzzle.get_string(matches.is_present("answers")),
+    };
+    write_output(matches, &output)
+}
+
+fn run_cloze(matches: &ArgMatches) -> Result<(), String> {
+    apply_seed(matches)?;
+    let settings = jumble::cloze::Settings {
+        letter_blank_count: parse_optional::<usize>(matches, "letters")?,
+    };
+    let cloze = jumble::cloze::Cloze::from_word_list(&get_word_list(matches)?, &settings)?;
+    write_output(matches, &cloze.get_string(matches.is_present("answers")))
+}
+
+fn run_book(matches: &ArgMatches) -> Result<(), String> {
+    apply_seed(matches)?;
+    let words = get_words(matches)?;
+    let count = parse_optional::<usize>(matches, "count")?.unwrap();
+    let groups = match matches.value_of("partition").unwrap() {
+        "length" => book::partition_by_length(&words, count)?,
+        "tag" => book::partition_by_tag(&get_word_list(matches)?, count)?,
+        _ => book::partition_random(&words, count)?,
+    };
+    let kind = match matches.value_of("kind").unwrap() {
+        "jumble" => book::PuzzleKind::Jumble(get_jumble_settings(matches)?),
+        _ => book::PuzzleKind::WordSearch(get_word_search_settings(matches)?),
+    };
+    let book = book::Book::generate(matches.value_of("title").unwrap(), &groups, &kind)?;
+    write_output(matches, &book.get_string())
+}
+
+fn run_word_list_sample(matches: &ArgMatches) -> Result<(), String> {
+    apply_seed(matches)?;
+    let list = get_word_list(matches)?;
+    let count = parse_optional::<usize>(matches, "count")?.unwrap();
+    let mut settings = SampleSettings::default();
+    if let Some(lengths) = matches.value_of("lengths") {
+        settings.length_bands = parse_length_bands(lengths)?;
+    }
+    settings.shared_letters_min = parse_optional::<usize>(matches, "shared-letters")?.unwrap_or(0);
+    for path in matches.values_of("exclude").into_iter().flatten() {
+        settings.exclude.append(&mut WordList::read_file(path).map_err(|e| e.to_string())?.get_words());
+    }
+    let words = settings.sample(&list, count)?;
+    // Keep the metadata of the chosen words for CSV and JSON output.
+    let sublist = list.filter(|entry| words.contains(&entry.word));
+    let output = match matches.value_of("format").unwrap() {
+        "rust" => sublist.to_rust_const("WORDS"),
+        "csv" => sublist.to_format_string(FileFormat::Csv),
+        "json" => sublist.to_format_string(FileFormat::Json),
+        _ => sublist.get_words().iter().map(|word| format!("{}\n", word)).join(""),
+    };
+    write_output(matches, &output)
+}
+
+fn run_word_list_convert(matches: &ArgMatches) -> Result<(), String> {
+    let list = get_word_list(matches)?;
+    for word in list.get_removed_duplicates().iter() {
+        eprintln!("Removed duplicate \"{}\".", word);
+    }
+    list.write_file(matches.value_of("output").unwrap()).map_err(|e| e.to_string())
+}
+
+fn run_word_list_lint(matches: &ArgMatches) -> Result<(), String> {
+    let list = get_word_list(matches)?;
+    let mut settings = LintSettings::default();
+    if let Some(length_max) = parse_optional::<usize>(matches, "length-max")? {
+        settings.length_max = length_max;
+    }
+    settings.allow_spaces = matches.is_present("allow-spaces");
+    let issues = word_list::lint_list(&list, &settings);
+    for issue in issues.iter() {
+        println!("{}", issue);
+    }
+    let error_count = issues.iter().filter(|issue| issue.severity == Severity::Error).count();
+    if error_count > 0 {
+        return Err(format!("Found {} errors in {} words.", error_count, list.len()));
+    }
+    println!("{} words checked, {} issues.", list.len(), issues.len());
+    Ok(())
+}
+
+fn get_word_list(matches: &ArgMatches) -> Result<WordList, String> {
+    let list = match matches.value_of("file") {
+        Some(path) => WordList::read_file(path).map_err(|e| e.to_string())?,
+        None => {
+            let name = matches.value_of("list").unwrap_or(DEFAULT_LIST);
+            WordList::named(name).ok_or_else(|| format!("There's no word list named \"{}\".", name))?
+        },
+    };
+    let list = match matches.value_of("tag") {
+        Some(tag) => list.filter_by_tag(tag),
+        None => list,
+    };
+    let list = match parse_optional::<u8>(matches, "grade")? {
+        Some(grade) => list.filter_by_grade(grade),
+        None => list,
+    };
+    if list.is_empty() {
+        return Err("The word list is empty or nothing matched the filters.".to_string());
+    }
+    Ok(list)
+}
+
+fn get_words(matches: &ArgMatches) -> Result<Vec<String>, String> {
+    Ok(get_word_list(matches)?.get_words().clone())
+}
+
+fn parse_length_bands(value: &str) -> Result<Vec<LengthBand>, String> {
+    let error = |band: &str| format!("\"{}\" should look like 3-5:4, meaning four words of three to five letters.", band);
+    let mut bands = vec![];
+    for band in value.split(',').map(|band| band.trim()).filter(|band| !band.is_empty()) {
+        let (lengths, count) = band.split_once(':').ok_or_else(|| error(band))?;
+        let (length_min, length_max) = lengths.split_once('-').unwrap_or((lengths, lengths));
+        let length_min = length_min.trim().parse::<usize>().map_err(|_| error(band))?;
+        let length_max = length_max.trim().parse::<usize>().map_err(|_| error(band))?;
+        let count = count.trim().parse::<usize>().map_err(|_| error(band))?;
+        if length_min > length_max {
+            return Err(error(band));
+        }
+        bands.push(LengthBand::new(length_min, length_max, count));
+    }
+    Ok(bands)
+}
+
+fn parse_directions(value: &str) -> Result<Vec<Direction>, String> {
+    if value.trim().eq_ignore_ascii_case("all") {
+        return Ok(Settings::default().directions);
+    }
+    let mut directions = vec![];
+    for name in value.split(',').filter(|name| !name.trim().is_empty()) {
+        let direction = Direction::from_name(name).ok_or_else(|| format!("\"{}\" is not a direction.", name.trim()))?;
+        if !directions.contains(&direction) {
+            directions.push(direction);
+        }
+    }
+    if directions.is_empty() {
+        return Err("At least one direction is required.".to_string());
+    }
+    Ok(directions)
+}
+
+fn parse_optional<T: std::str::FromStr>(matches: &ArgMatches, name: &str) -> Result<Option<T>, String> {
+    match matches.value_of(name) {
+        Some(value) => value.trim().parse::<T>()
+            .map(Some)
+            .map_err(|_| format!("\"{}\" is not a valid value for --{}.", value, name)),
+        None => Ok(None),
+    }
+}
+
+fn apply_seed(matches: &ArgMatches) -> Result<(), String> {
+    if let Some(seed) = parse_optional::<u64>(matches, "seed")? {
+        random::set_seed(seed);
+    }
+    Ok(())
+}
+
+fn write_output(matches: &ArgMatches, output: &str) -> Result<(), String> {
+    match matches.value_of("output") {
+        Some(path) => std::fs::write(path, output).map_err(|e| format!("Unable to write \"{}\": {}", path, e)),
+        None => {
+            print!("{}", output);
+            Ok(())
+        },
+    }
+}
@@ -0,0 +1,424 @@
+use crate::*;
+
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+pub const DIRECTIONS: [Direction; 8] = [Direction::N, Direction::NE, Direction::E, Direction::SE, Direction::S, Direction::SW, Direction::W, Direction::NW];
+
+pub type Offset = [isize; 2];
+
+/// A rectangle of cells stored row by row in a single Vec. Positions run from (0, 0) at the top
+/// left, with x increasing to the right and y increasing downward.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+/// The cells along a straight line, from Grid::get_line.
+pub struct LineIter<'a, T> {
+    grid: &'a Grid<T>,
+    index: isize,
+    step: isize,
+    remaining: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A copy of the grid turned a quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |position| self[&Position::new(position.y, self.height - 1 - position.x)].clone())
+    }
+
+    /// A copy of the grid turned a quarter turn counterclockwise, so the top row becomes the left
+    /// column.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |position| self[&Position::new(self.width - 1 - position.y, position.x)].clone())
+    }
+
+    /// A mirror image with the left and right sides swapped.
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.width, self.height, |position| self[&Position::new(self.width - 1 - position.x, position.y)].clone())
+    }
+
+    /// A mirror image with the top and bottom swapped.
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width, self.height, |position| self[&Position::new(position.x, self.height - 1 - position.y)].clone())
+    }
+
+    /// A copy with rows and columns swapped.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |position| self[&Position::new(position.y, position.x)].clone())
+    }
+
+    /// A copy of the rectangle with the given corners, inclusive. Returns None if either corner is
+    /// off the grid or they're out of order.
+    pub fn crop(&self, top_left: &Position, bottom_right: &Position) -> Option<Self> {
+        if !self.contains(top_left) || !self.contains(bottom_right) || top_left.x > bottom_right.x || top_left.y > bottom_right.y {
+            return None;
+        }
+        Some(Self::from_fn(bottom_right.x - top_left.x + 1, bottom_right.y - top_left.y + 1,
+            |position| self[&Position::new(top_left.x + position.x, top_left.y + position.y)].clone()))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+        where F: FnMut(&Position) -> T
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(&Position::new(x, y)));
+            }
+        }
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// All rows must be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!("Row {} has {} cells but the first row has {}.", y, rows[y].len(), width));
+        }
+        Ok(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    #[inline]
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn contains(&self, position: &Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[self.get_index(position)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.get_index(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_xy(&self, x: usize, y: usize) -> Option<&T> {
+        self.get(&Position::new(x, y))
+    }
+
+    pub fn get_mut_xy(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.get_mut(&Position::new(x, y))
+    }
+
+    #[inline]
+    fn get_index(&self, position: &Position) -> usize {
+        debug_assert!(self.contains(position));
+        (position.y * self.width) + position.x
+    }
+
+    /// The position one step away in the given direction, or None if that's off the grid.
+    pub fn get_neighbor(&self, position: &Position, direction: Direction) -> Option<Position> {
+        position.offset(direction, 1).filter(|neighbor| self.contains(neighbor))
+    }
+
+    /// The neighbors of a position that are on the grid, with the direction to each one.
+    pub fn get_neighbors(&self, position: &Position) -> Vec<(Direction, Position)> {
+        DIRECTIONS.iter()
+            .filter_map(|direction| self.get_neighbor(position, *direction).map(|neighbor| (*direction, neighbor)))
+            .collect()
+    }
+
+    /// The positions of length cells in a straight line from start, or None if any of them would
+    /// be off the grid.
+    pub fn get_line_positions(&self, start: &Position, direction: Direction, length: usize) -> Option<Vec<Position>> {
+        self.check_line(start, direction, length)?;
+        Some((0..length).map(|steps| start.offset(direction, steps).unwrap()).collect())
+    }
+
+    /// The cells in a straight line from start, or None if any of them would be off the grid.
+    pub fn get_line(&self, start: &Position, direction: Direction, length: usize) -> Option<LineIter<'_, T>> {
+        self.check_line(start, direction, length)?;
+        let offset = direction.get_offset();
+        Some(LineIter {
+            grid: self,
+            index: self.get_index(start) as isize,
+            step: (offset[1] * self.width as isize) + offset[0],
+            remaining: length,
+        })
+    }
+
+    /// The cells from start to the edge of the grid in the given direction, including start.
+    pub fn get_line_to_edge(&self, start: &Position, direction: Direction) -> LineIter<'_, T> {
+        let mut length = 0;
+        let mut position = Some(start.clone()).filter(|position| self.contains(position));
+        while let Some(current) = position {
+            length += 1;
+            position = self.get_neighbor(&current, direction);
+        }
+        match self.get_line(start, direction, length) {
+            Some(line) => line,
+            None => LineIter { grid: self, index: 0, step: 0, remaining: 0 },
+        }
+    }
+
+    // Both ends of the line have to be on the grid. Since it's straight, everything in between is
+    // too.
+    fn check_line(&self, start: &Position, direction: Direction, length: usize) -> Option<()> {
+        if length == 0 {
+            return if self.contains(start) { Some(()) } else { None };
+        }
+        let end = start.offset(direction, length - 1)?;
+        if self.contains(start) && self.contains(&end) {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    pub fn get_row(&self, y: usize) -> &[T] {
+        &self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    pub fn get_row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    pub fn get_column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(if x < self.width { self.height } else { 0 })
+    }
+
+    pub fn get_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(index, cell)| (Position::new(index % width, index / width), cell))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        let width = self.width;
+        self.cells.iter_mut().enumerate().map(move |(index, cell)| (Position::new(index % width, index / width), cell))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+        where F: Fn(&T) -> U
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<&Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: &Position) -> &T {
+        assert!(self.contains(position), "{} is outside the {}x{} grid.", position, self.width, self.height);
+        &self.cells[self.get_index(position)]
+    }
+}
+
+impl<T> IndexMut<&Position> for Grid<T> {
+    fn index_mut(&mut self, position: &Position) -> &mut T {
+        assert!(self.contains(position), "{} is outside the {}x{} grid.", position, self.width, self.height);
+        let index = self.get_index(position);
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        for row in self.get_rows() {
+            writeln!(f, "{}", row.iter().join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> Iterator for LineIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let cell = &self.grid.cells[self.index as usize];
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.index += self.step;
+        }
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for LineIter<'a, T> {}
+
+impl Position {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self {
+            x,
+            y
+        }
+    }
+
+    #[inline]
+    pub fn get_x(&self) -> usize {
+        self.x
+    }
+
+    #[inline]
+    pub fn get_y(&self) -> usize {
+        self.y
+    }
+
+    /// The position the given number of steps away, or None if that would take x or y below zero.
+    /// It may still be past the right or bottom edge of a grid.
+    pub fn offset(&self, direction: Direction, steps: usize) -> Option<Self> {
+        let offset = direction.get_offset();
+        let steps = isize::try_from(steps).ok()?;
+        let x = isize::try_from(self.x).ok()?.checked_add(steps.checked_mul(offset[0])?)?;
+        let y = isize::try_from(self.y).ok()?.checked_add(steps.checked_mul(offset[1])?)?;
+        if x >= 0 && y >= 0 {
+            Some(Self::new(x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "({}, {})", fc(self.x), fc(self.y))
+    }
+}
+
+impl Direction {
+    pub fn get_offset(&self) -> Offset {
+        match self {
+            Direction::N => [0, -1],
+            Direction::NE => [1, -1],
+            Direction::E => [1, 0],
+            Direction::SE => [1, 1],
+            Direction::S => [0, 1],
+            Direction::SW => [-1, 1],
+            Direction::W => [-1, 0],
+            Direction::NW => [-1, -1],
+        }
+    }
+
+    /// The line the direction runs along, ignoring which way: 0 is N-S, 1 is NE-SW, 2 is E-W and 3
+    /// is SE-NW.
+    pub fn get_axis(&self) -> usize {
+        match self {
+            Direction::N | Direction::S => 0,
+            Direction::NE | Direction::SW => 1,
+            Direction::E | Direction::W => 2,
+            Direction::SE | Direction::NW => 3,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::N => Direction::S,
+            Direction::NE => Direction::SW,
+            Direction::E => Direction::W,
+            Direction::SE => Direction::NW,
+            Direction::S => Direction::N,
+            Direction::SW => Direction::NE,
+            Direction::W => Direction::E,
+            Direction::NW => Direction::SE,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        DIRECTIONS.iter()
+            .find(|direction| direction.get_variant_name().eq_ignore_ascii_case(name.trim()))
+            .cloned()
+    }
+
+    pub fn get_variant_name(&self) -> &str {
+        match self {
+            Direction::N => "N",
+            Direction::NE => "NE",
+            Direction::E => "E",
+            Direction::SE => "SE",
+            Direction::S => "S",
+            Direction::SW => "SW",
+            Direction::W => "W",
+            Direction::NW => "NW",
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.get_variant_name())
+    }
+}
+
+pub fn main() {
+    let grid = Grid::from_rows(vec!["cat".chars().collect(), "dog".chars().collect()]).unwrap();
+    println!("{}", grid);
+    println!("{}", grid.rotate_clockwise());
+    println!("{}", grid.transpose());
+    let diagonal = grid.get_line(&Position::new(0, 0), Direction::SE, 2).unwrap().collect::<String>();
+    println!("Diagonal from the top left: {}", diagonal);
+}
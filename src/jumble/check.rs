@@ -0,0 +1,205 @@
+use super::dictionary::Dictionary;
+use super::puzzle::{self, Mode, Phrase, Puzzle, Settings};
+use super::puzzle::{get_graphemes, is_letter};
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The result of checking a student's answers to a jumble, one PhraseCheck per phrase in the
+/// order the puzzle shows them.
+#[derive(Clone, Debug, Serialize)]
+pub struct Check {
+    phrases: Vec<PhraseCheck>,
+    score: f32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PhraseCheck {
+    answer: String,
+    /// For each letter the student had to fill in, whether it matches the phrase. In
+    /// Mode::Scramble that's every letter.
+    blanks: Vec<bool>,
+    /// The answer isn't the phrase, but it uses the right letters and every word it changes is in
+    /// the dictionary, so it counts as correct.
+    is_alternative: bool,
+}
+
+impl Check {
+    pub fn get_phrase_checks(&self) -> &Vec<PhraseCheck> {
+        &self.phrases
+    }
+
+    /// The fraction of letters filled in correctly, from 0.0 to 1.0. Every letter of an
+    /// alternative answer counts as correct.
+    pub fn get_score(&self) -> f32 {
+        self.score
+    }
+
+    pub fn get_correct_count(&self) -> usize {
+        self.phrases.iter().filter(|phrase| phrase.is_correct()).count()
+    }
+}
+
+impl PhraseCheck {
+    pub fn get_answer(&self) -> &str {
+        &self.answer
+    }
+
+    pub fn get_blanks(&self) -> &Vec<bool> {
+        &self.blanks
+    }
+
+    pub fn is_alternative(&self) -> bool {
+        self.is_alternative
+    }
+
+    pub fn is_correct(&self) -> bool {
+        self.is_alternative || self.blanks.iter().all(|is_correct| *is_correct)
+    }
+
+    pub fn get_correct_count(&self) -> usize {
+        if self.is_alternative {
+            self.blanks.len()
+        } else {
+            self.blanks.iter().filter(|is_correct| **is_correct).count()
+        }
+    }
+}
+
+/// Check one answer per phrase, each written out in full with the blanks filled in or the words
+/// unscrambled. Case is ignored. An answer of the wrong length has every letter wrong.
+pub fn check(puzzle: &Puzzle, answers: &[&str]) -> Result<Check, String> {
+    let phrases = puzzle.get_phrases();
+    if answers.len() != phrases.len() {
+        return Err(format!("The puzzle has {} phrases but there are {} answers.", phrases.len(), answers.len()));
+    }
+    let settings = puzzle.get_settings();
+    let is_shared = !puzzle.get_letter_bank().is_empty();
+    // With a shared letter bank, a phrase can use any letters from the bank, so the letters are
+    // checked across the whole puzzle below.
+    let mut phrase_checks = phrases.iter()
+        .zip(answers.iter())
+        .map(|(phrase, answer)| check_phrase(phrase, answer, settings.mode, &settings.dictionary, !is_shared))
+        .collect::<Vec<_>>();
+    // Each alternative answer only has to use letters from the bank, but together the answers
+    // that count as correct can't use more of a letter than the bank has. If they do, only the
+    // exact answers count.
+    if is_shared && phrase_checks.iter().any(|phrase_check| phrase_check.is_alternative) {
+        let mut remaining: BTreeMap<String, usize> = BTreeMap::new();
+        for letter in puzzle.get_letter_bank().iter() {
+            *remaining.entry(letter.to_lowercase()).or_insert(0) += 1;
+        }
+        let used = phrases.iter()
+            .zip(answers.iter())
+            .zip(phrase_checks.iter())
+            .filter(|(_, phrase_check)| phrase_check.is_correct())
+            .flat_map(|((phrase, answer), _)| get_fill_in_letters(phrase, answer, settings.mode));
+        let mut fits_bank = true;
+        for letter in used {
+            match remaining.get_mut(&letter).filter(|count| **count > 0) {
+                Some(count) => *count -= 1,
+                None => fits_bank = false,
+            }
+        }
+        if !fits_bank {
+            phrase_checks.iter_mut().for_each(|phrase_check| phrase_check.is_alternative = false);
+        }
+    }
+    let blank_count: usize = phrase_checks.iter().map(|phrase_check| phrase_check.blanks.len()).sum();
+    let correct_count: usize = phrase_checks.iter().map(|phrase_check| phrase_check.get_correct_count()).sum();
+    let score = if blank_count == 0 { 1.0 } else { correct_count as f32 / blank_count as f32 };
+    Ok(Check {
+        phrases: phrase_checks,
+        score,
+    })
+}
+
+fn check_phrase(phrase: &Phrase, answer: &str, mode: Mode, dictionary: &Dictionary, check_letters: bool) -> PhraseCheck {
+    let answer = answer.trim().to_string();
+    let expected = phrase.get_graphemes();
+    let actual = get_graphemes(&answer);
+    let fill_in_indexes = get_fill_in_indexes(phrase, mode);
+    if actual.len() != expected.len() {
+        return PhraseCheck {
+            answer,
+            blanks: vec![false; fill_in_indexes.len()],
+            is_alternative: false,
+        };
+    }
+    let blanks = fill_in_indexes.iter()
+        .map(|index| is_same_letter(&actual[*index], &expected[*index]))
+        .collect::<Vec<_>>();
+    let is_alternative = !blanks.iter().all(|is_correct| *is_correct)
+        && is_valid_alternative(phrase, &actual, &fill_in_indexes, mode, dictionary, check_letters);
+    PhraseCheck {
+        answer,
+        blanks,
+        is_alternative,
+    }
+}
+
+// The letters are the ones the puzzle gave, everything else is unchanged, and every word with a
+// letter filled in is a dictionary word.
+fn is_valid_alternative(phrase: &Phrase, actual: &[String], fill_in_indexes: &[usize], mode: Mode, dictionary: &Dictionary, check_letters: bool) -> bool {
+    let expected = phrase.get_graphemes();
+    let is_unchanged = (0..expected.len())
+        .filter(|index| !fill_in_indexes.contains(index))
+        .all(|index| is_same_letter(&actual[index], &expected[index]));
+    if !is_unchanged || fill_in_indexes.iter().any(|index| !is_letter(&actual[*index])) {
+        return false;
+    }
+    let word_ranges = puzzle::get_word_ranges(expected);
+    if check_letters {
+        // A scrambled word has to be unscrambled on its own, but missing letters can go in any
+        // blank in the phrase.
+        let letter_groups = match mode {
+            Mode::Blanks => vec![fill_in_indexes.to_vec()],
+            Mode::Scramble => word_ranges.iter().map(|(start, end)| (*start..*end).collect()).collect(),
+        };
+        for indexes in letter_groups {
+            let mut expected_letters = indexes.iter().map(|index| expected[*index].to_lowercase()).collect::<Vec<_>>();
+            let mut actual_letters = indexes.iter().map(|index| actual[*index].to_lowercase()).collect::<Vec<_>>();
+            expected_letters.sort();
+            actual_letters.sort();
+            if expected_letters != actual_letters {
+                return false;
+            }
+        }
+    }
+    word_ranges.iter()
+        .filter(|(start, end)| fill_in_indexes.iter().any(|index| index >= start && index < end))
+        .all(|(start, end)| dictionary.contains(&actual[*start..*end].concat()))
+}
+
+fn get_fill_in_indexes(phrase: &Phrase, mode: Mode) -> Vec<usize> {
+    match mode {
+        Mode::Blanks => phrase.get_blank_indexes().clone(),
+        Mode::Scramble => phrase.get_graphemes().iter()
+            .enumerate()
+            .filter(|(_, grapheme)| is_letter(grapheme))
+            .map(|(index, _)| index)
+            .collect(),
+    }
+}
+
+fn get_fill_in_letters(phrase: &Phrase, answer: &str, mode: Mode) -> Vec<String> {
+    let actual = get_graphemes(answer.trim());
+    get_fill_in_indexes(phrase, mode).iter()
+        .filter_map(|index| actual.get(*index))
+        .map(|letter| letter.to_lowercase())
+        .collect()
+}
+
+fn is_same_letter(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+pub fn main() {
+    let mut puzzle = Puzzle::new(&["cat", "the dog ran"], &Settings::default()).unwrap();
+    puzzle.build().unwrap();
+    println!("{}", puzzle.get_string(false));
+    let answers = puzzle.get_phrases().iter().map(|phrase| phrase.get_phrase()).collect::<Vec<_>>();
+    println!("{:?}", puzzle.check(&answers).unwrap());
+    let answers = vec!["xxx"; answers.len()];
+    println!("{:?}", puzzle.check(&answers).unwrap());
+}
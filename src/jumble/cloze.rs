@@ -0,0 +1,181 @@
+use crate::*;
+use crate::random::rng;
+use crate::word_list::WordList;
+use super::puzzle::{get_graphemes, get_word_ranges, is_letter, Phrase};
+
+use rand::seq::SliceRandom;
+
+// Regular endings a sentence can add to the target word, as in "jumps" or "jumped" for "jump".
+const ENDINGS: [&str; 8] = ["s", "es", "d", "ed", "ing", "er", "est", "ly"];
+
+/// A vocabulary fill-in-the-blank worksheet. Each item is an example sentence with its target
+/// word blanked out, and the target words are listed in a shuffled word bank.
+pub struct Cloze {
+    items: Vec<ClozeItem>,
+    word_bank: Vec<String>,
+}
+
+pub struct ClozeItem {
+    word: String,
+    phrase: Phrase,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Settings {
+    /// If set, blank only this many letters of each target word instead of the whole word. The
+    /// first letter is always left showing.
+    pub letter_blank_count: Option<usize>,
+}
+
+impl Cloze {
+    /// Each item is a target word and an example sentence that uses it. The sentence can add a
+    /// regular ending to the word, such as "jumped" for "jump". A target of more than one word,
+    /// like "ice cream", has to appear in the sentence with the words in a row.
+    pub fn new(items: &[(&str, &str)], settings: &Settings) -> Result<Self, String> {
+        if items.is_empty() {
+            return Err("A cloze worksheet needs at least one sentence.".to_string());
+        }
+        let mut items = items.iter()
+            .map(|(word, sentence)| ClozeItem::new(word, sentence, settings))
+            .collect::<Result<Vec<_>, _>>()?;
+        items.shuffle(&mut rng());
+        let mut word_bank = items.iter().map(|item| item.word.clone()).unique().collect::<Vec<_>>();
+        word_bank.shuffle(&mut rng());
+        Ok(Self {
+            items,
+            word_bank,
+        })
+    }
+
+    /// Use the words in the list that have an example sentence.
+    pub fn from_word_list(list: &WordList, settings: &Settings) -> Result<Self, String> {
+        let items = list.get_entries().iter()
+            .filter_map(|entry| entry.example.as_ref().map(|example| (entry.word.as_str(), example.as_str())))
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            return Err(format!("None of the words in \"{}\" have an example sentence.", list.get_name()));
+        }
+        Self::new(&items, settings)
+    }
+
+    pub fn print(&self, show_answers: bool) {
+        println!("\n=============================================================================\n");
+        println!("{}", self.get_string(show_answers));
+        println!("\n-----------------------------------------------------------------------------\n");
+    }
+
+    pub fn get_string(&self, show_answers: bool) -> String {
+        let mut s = format!("Word bank: {}\n\n", self.word_bank.join("  "));
+        for (index, item) in self.items.iter().enumerate() {
+            s.push_str(&format!("{}. {}\n", index + 1, item.phrase.get_jumble()));
+            if show_answers {
+                s.push_str(&format!("   {}\n", item.phrase.get_phrase()));
+            }
+        }
+        s
+    }
+
+    pub fn get_answer_string(&self) -> String {
+        self.items.iter()
+            .enumerate()
+            .map(|(index, item)| format!("{}. {}\n", index + 1, item.word))
+            .join("")
+    }
+
+    pub fn get_items(&self) -> &Vec<ClozeItem> {
+        &self.items
+    }
+
+    pub fn get_word_bank(&self) -> &Vec<String> {
+        &self.word_bank
+    }
+}
+
+impl ClozeItem {
+    fn new(word: &str, sentence: &str, settings: &Settings) -> Result<Self, String> {
+        let word = word.trim();
+        let mut phrase = Phrase::new(sentence.trim())?;
+        let (start, end) = find_word(&phrase, word)
+            .ok_or_else(|| format!("\"{}\" isn't in the sentence \"{}\".", word, sentence.trim()))?;
+        // Only letters are blanked, not the spaces between the words of a multi-word target.
+        let letter_indexes = (start..end).filter(|index| is_letter(&phrase.get_graphemes()[*index])).collect::<Vec<_>>();
+        let blank_indexes = match settings.letter_blank_count {
+            // A one-letter word can't keep its first letter, so the whole word is blanked.
+            Some(letter_blank_count) if letter_indexes.len() > 1 => {
+                let mut indexes = letter_indexes[1..].to_vec();
+                indexes.shuffle(&mut rng());
+                indexes.truncate(letter_blank_count.max(1));
+                indexes
+            },
+            _ => letter_indexes,
+        };
+        phrase.set_blank_indexes(&blank_indexes)?;
+        Ok(Self {
+            word: word.to_string(),
+            phrase,
+        })
+    }
+
+    pub fn get_word(&self) -> &str {
+        &self.word
+    }
+
+    pub fn get_phrase(&self) -> &Phrase {
+        &self.phrase
+    }
+}
+
+// The grapheme range of the target in the sentence, ignoring case. Each word of the target has to
+// match a whole word of the sentence, so "cat" isn't found in "catalog". An exact match is best,
+// otherwise the last word can have one of the endings, as "runs" does for "run".
+fn find_word(phrase: &Phrase, word: &str) -> Option<(usize, usize)> {
+    let word_graphemes = get_graphemes(&word.to_lowercase());
+    let words = get_word_ranges(&word_graphemes).iter()
+        .map(|(start, end)| word_graphemes[*start..*end].concat())
+        .collect::<Vec<_>>();
+    if words.is_empty() {
+        return None;
+    }
+    let graphemes = phrase.get_graphemes();
+    let ranges = phrase.get_word_ranges();
+    let get_text = |(start, end): &(usize, usize)| graphemes[*start..*end].concat().to_lowercase();
+    let find = |is_last_match: fn(&str, &str) -> bool| ranges.windows(words.len())
+        .find(|window| window.iter().zip(words.iter()).enumerate().all(|(index, (range, word))| {
+            if index + 1 == words.len() { is_last_match(&get_text(range), word) } else { get_text(range) == *word }
+        }))
+        .map(|window| (window[0].0, window[window.len() - 1].1));
+    find(|text, word| text == word).or_else(|| find(has_ending))
+}
+
+// Whether the text is the word with one of the endings, allowing for a doubled last letter as in
+// "running", a dropped "e" as in "making", or a "y" changed to "i" as in "happier".
+fn has_ending(text: &str, word: &str) -> bool {
+    let mut stems = vec![word.to_string()];
+    if let Some(last) = word.chars().last() {
+        stems.push(format!("{}{}", word, last));
+    }
+    if let Some(stem) = word.strip_suffix('e') {
+        stems.push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix('y') {
+        stems.push(format!("{}i", stem));
+    }
+    stems.iter().any(|stem| text.strip_prefix(stem.as_str()).is_some_and(|ending| ENDINGS.contains(&ending)))
+}
+
+pub fn main() {
+    let items = [
+        ("enormous", "The whale was so enormous that it looked like an island."),
+        ("cooperation", "With everyone's cooperation, we cleaned the park in an hour."),
+        ("annoy", "The buzzing fly began to annoy the cat."),
+        ("helpful", "A map is helpful when you're lost."),
+        ("suppose", "I suppose we could walk if the bus is late."),
+    ];
+    Cloze::new(&items, &Settings::default()).unwrap().print(true);
+    let settings = Settings {
+        letter_blank_count: Some(3),
+    };
+    let cloze = Cloze::new(&items, &settings).unwrap();
+    cloze.print(false);
+    println!("{}", cloze.get_answer_string());
+}
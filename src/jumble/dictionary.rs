@@ -0,0 +1,74 @@
+use crate::*;
+use crate::word_list::{WordList, WordListError};
+
+use std::collections::BTreeSet;
+
+const BUILT_IN_LIST_NAMES: [&str; 5] = ["WORDS_1", "WORDS_2", "WORDS_3", "WORDS_4", "ALL_SECOND_GRADE"];
+
+/// A set of known words, used to make sure that a scrambled word doesn't spell some other word.
+/// Case is ignored.
+#[derive(Clone, Debug, Default)]
+pub struct Dictionary {
+    words: BTreeSet<String>,
+}
+
+impl Dictionary {
+    pub fn new<S: AsRef<str>>(words: &[S]) -> Self {
+        let mut dictionary = Self::default();
+        dictionary.extend(words);
+        dictionary
+    }
+
+    /// All of the words in the built-in word lists. This is small, so for real puzzles it's
+    /// worth adding a proper word list with read_file or extend.
+    pub fn built_in() -> Self {
+        let mut dictionary = Self::default();
+        for name in BUILT_IN_LIST_NAMES.iter() {
+            dictionary.extend(word_list::get_named_list(name).unwrap());
+        }
+        dictionary
+    }
+
+    pub fn from_word_list(list: &WordList) -> Self {
+        Self::new(&list.get_words())
+    }
+
+    /// Read a word list file in any of the formats WordList supports.
+    pub fn read_file(path: &str) -> Result<Self, WordListError> {
+        Ok(Self::from_word_list(&WordList::read_file(path)?))
+    }
+
+    pub fn insert(&mut self, word: &str) {
+        let word = word.trim().to_lowercase();
+        if !word.is_empty() {
+            self.words.insert(word);
+        }
+    }
+
+    pub fn extend<S: AsRef<str>>(&mut self, words: &[S]) {
+        words.iter().for_each(|word| self.insert(word.as_ref()));
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.trim().to_lowercase())
+    }
+
+    /// Whether any word starts with this prefix, including the prefix itself.
+    pub fn contains_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.trim().to_lowercase();
+        self.words.range(prefix.clone()..).next().is_some_and(|word| word.starts_with(&prefix))
+    }
+
+    /// The words in alphabetical order, all in lowercase.
+    pub fn get_words(&self) -> &BTreeSet<String> {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
@@ -1 +1,7 @@
+pub mod check;
+pub mod cloze;
+pub mod dictionary;
+pub mod phrase_list;
 pub mod puzzle;
+pub mod riddle;
+pub mod worksheet;
@@ -0,0 +1,112 @@
+use crate::word_list::{self, FileFormat, WordListError};
+
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+const CSV_HEADERS: [&str; 4] = ["phrase", "hint", "category", "blanks"];
+
+/// Phrases for a jumble, each with optional extras that a plain word list doesn't have.
+#[derive(Clone, Debug, Default)]
+pub struct PhraseList {
+    entries: Vec<PhraseEntry>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct PhraseEntry {
+    pub phrase: String,
+    /// Printed with the jumble to help the solver.
+    #[serde(default)]
+    pub hint: Option<String>,
+    /// Phrases with the same category are printed together under it.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Overrides Settings::blank_count and Settings::blank_percent for this phrase.
+    #[serde(default, rename = "blanks")]
+    pub blank_count: Option<usize>,
+}
+
+// As with word lists, each item in a JSON file can be a plain phrase or an object.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonPhraseEntry {
+    Phrase(String),
+    Entry(PhraseEntry),
+}
+
+impl PhraseList {
+    /// Phrases are trimmed, blank ones are skipped, and any phrase that repeats an earlier one
+    /// (ignoring case) is dropped.
+    pub fn from_entries(entries: Vec<PhraseEntry>) -> Self {
+        let mut seen = BTreeSet::new();
+        let entries = entries.into_iter()
+            .map(|mut entry| {
+                entry.phrase = entry.phrase.trim().to_string();
+                entry
+            })
+            .filter(|entry| !entry.phrase.is_empty() && seen.insert(entry.phrase.to_lowercase()))
+            .collect();
+        Self {
+            entries,
+        }
+    }
+
+    /// Read a .txt file with one phrase per line, a .csv file with a header row naming any of the
+    /// columns phrase, hint, category and blanks, or a .json array of phrases or objects with
+    /// those fields.
+    pub fn read_file(path: &str) -> Result<Self, WordListError> {
+        let format = FileFormat::from_path(path)?;
+        let text = std::fs::read_to_string(path).map_err(|error| WordListError::Io { path: path.to_string(), error })?;
+        Self::parse(&text, format).map_err(|message| WordListError::Parse { path: path.to_string(), message })
+    }
+
+    pub fn parse(text: &str, format: FileFormat) -> Result<Self, String> {
+        match format {
+            FileFormat::Text => Ok(Self::from_entries(word_list::parse_text_lines(text).into_iter()
+                .map(|phrase| PhraseEntry { phrase, ..Default::default() })
+                .collect())),
+            FileFormat::Csv => Self::parse_csv(text),
+            FileFormat::Json => Self::parse_json(text),
+        }
+    }
+
+    fn parse_csv(text: &str) -> Result<Self, String> {
+        // Same rules as for word lists: without a header row starting with "phrase" there's only
+        // the phrase column.
+        let mut entries = vec![];
+        for (row_number, mut cells) in word_list::parse_csv_rows(text, &CSV_HEADERS)? {
+            let blank_count = cells[3].as_deref()
+                .map(|blank_count| blank_count.parse::<usize>().map_err(|_| format!("Row {}: \"{}\" is not a number of blanks.", row_number, blank_count)))
+                .transpose()?;
+            entries.push(PhraseEntry {
+                phrase: cells[0].take().unwrap_or_default(),
+                hint: cells[1].take(),
+                category: cells[2].take(),
+                blank_count,
+            });
+        }
+        Ok(Self::from_entries(entries))
+    }
+
+    fn parse_json(text: &str) -> Result<Self, String> {
+        let entries = serde_json::from_str::<Vec<JsonPhraseEntry>>(text).map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|entry| match entry {
+                JsonPhraseEntry::Phrase(phrase) => PhraseEntry { phrase, ..Default::default() },
+                JsonPhraseEntry::Entry(entry) => entry,
+            })
+            .collect();
+        Ok(Self::from_entries(entries))
+    }
+
+    pub fn get_entries(&self) -> &Vec<PhraseEntry> {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -1,107 +1,703 @@
 use crate::*;
-use rand::thread_rng;
+use crate::random::rng;
+use super::check::{self, Check};
+use super::dictionary::Dictionary;
+use super::phrase_list::PhraseList;
+use super::riddle::{self, Riddle};
 use rand::seq::SliceRandom;
+use std::collections::BTreeMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+const TRY_COUNT_MAX: usize = 100;
+// How many partial answers counting the solutions may try before giving up, so that a puzzle with
+// too many blanks fails rather than hangs.
+const SEARCH_NODE_MAX: usize = 1_000_000;
+const VOWELS: &str = "aeiouàáâãäåæèéêëìíîïòóôõöøùúûü";
 
 pub struct Puzzle {
     phrases: Vec<Phrase>,
-    blank_count: usize,
+    settings: Settings,
+    riddle: Option<Riddle>,
+    letter_bank: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Settings {
+    pub mode: Mode,
+    /// How many letters to blank out of each phrase in Mode::Blanks.
+    pub blank_count: usize,
+    /// If set, blank this percentage of the letters in each word instead of blank_count, leaving
+    /// at least one letter of each word showing. A phrase always gets at least two blanks.
+    pub blank_percent: Option<usize>,
+    /// Which letters to favor when choosing blanks.
+    pub difficulty: Difficulty,
+    /// A scrambled word must not spell any of these words. The phrases in the puzzle are added
+    /// to it automatically.
+    pub dictionary: Dictionary,
+    /// In Mode::Blanks, only accept blanks that can be filled from the missing letters in just
+    /// one way that makes every word a dictionary word. The built-in dictionary has too few words
+    /// for that to mean much, so add a real word list to it.
+    pub require_unique_solution: bool,
+    /// In Mode::Blanks, pool the missing letters of every phrase into one letter bank instead of
+    /// listing them with each phrase. The blanks are chosen so that there's only one way to
+    /// distribute the bank that makes every word a dictionary word.
+    pub shared_letter_bank: bool,
+    /// For a classic jumble with a riddle, how many words to circle letters in.
+    pub riddle_word_count: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// Replace some of the letters with blanks and list the missing letters.
+    Blanks,
+    /// Mix up the letters of each word, leaving the spaces and punctuation where they are.
+    Scramble,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    /// Favor vowels and never blank the first letter of a word.
+    Easy,
+    /// Every letter is equally likely to be blanked.
+    Medium,
+    /// Favor consonants next to other consonants and letters that could be swapped for another
+    /// letter to make a different word.
+    Hard,
 }
 
 pub struct Phrase {
     phrase: String,
+    graphemes: Vec<String>,
     jumble: String,
-    missing_chars: Vec<char>,
-    char_indexes: Vec<usize>,
+    missing_letters: Vec<String>,
+    letter_indexes: Vec<usize>,
+    blank_indexes: Vec<usize>,
+    circled_indexes: Vec<usize>,
+    hint: Option<String>,
+    category: Option<String>,
+    blank_count: Option<usize>,
 }
 
 impl Puzzle {
-    pub fn new(phrases: &[&str], blank_count: usize) -> Self {
-        let mut phrases = phrases.iter().map(|phrase| Phrase::new(phrase)).collect::<Vec<_>>();
-        phrases.shuffle(&mut thread_rng());
+    pub fn new(phrases: &[&str], settings: &Settings) -> Result<Self, String> {
+        if phrases.is_empty() {
+            return Err("A jumble needs at least one phrase.".to_string());
+        }
+        let phrases = phrases.iter().map(|phrase| Phrase::new(phrase)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_phrases(phrases, settings))
+    }
+
+    /// Use the phrases from a file along with their hints, categories and blank counts. Phrases
+    /// in the same category are kept together.
+    pub fn from_phrase_list(list: &PhraseList, settings: &Settings) -> Result<Self, String> {
+        if list.is_empty() {
+            return Err("A jumble needs at least one phrase.".to_string());
+        }
+        let mut phrases = vec![];
+        for entry in list.get_entries().iter() {
+            let mut phrase = Phrase::new(&entry.phrase)?;
+            phrase.hint = entry.hint.clone();
+            phrase.category = entry.category.clone();
+            phrase.blank_count = entry.blank_count;
+            phrases.push(phrase);
+        }
+        let mut puzzle = Self::from_phrases(phrases, settings);
+        // The sort is stable, so the phrases are still shuffled within each category.
+        puzzle.phrases.sort_by(|a, b| a.category.cmp(&b.category));
+        Ok(puzzle)
+    }
+
+    fn from_phrases(mut phrases: Vec<Phrase>, settings: &Settings) -> Self {
+        let mut settings = settings.clone();
+        for phrase in phrases.iter() {
+            settings.dictionary.extend(&get_words(&phrase.phrase));
+        }
+        phrases.shuffle(&mut rng());
         Self {
             phrases,
-            blank_count,
+            settings,
+            riddle: None,
+            letter_bank: vec![],
         }
     }
 
-    pub fn build(&mut self) {
+    /// A classic newspaper jumble: scrambled words chosen from the list, with some letters of each
+    /// answer circled. Unscrambled, the circled letters spell the answer to the riddle.
+    pub fn new_classic(riddle: &Riddle, words: &[&str], settings: &Settings) -> Result<Self, String> {
+        let chosen = riddle::choose_circled_words(&riddle.get_letters(), words, settings.riddle_word_count)?;
+        let mut settings = settings.clone();
+        settings.mode = Mode::Scramble;
+        // The other words in the list are likely to be real words, so a scramble shouldn't spell
+        // one of them either.
+        settings.dictionary.extend(words);
+        let phrases = chosen.iter().map(|(word, _)| word.as_str()).collect::<Vec<_>>();
+        let mut puzzle = Self::new(&phrases, &settings)?;
+        for phrase in puzzle.phrases.iter_mut() {
+            phrase.circled_indexes = chosen.iter().find(|(word, _)| *word == phrase.phrase).unwrap().1.clone();
+        }
+        puzzle.riddle = Some(riddle.clone());
+        Ok(puzzle)
+    }
+
+    pub fn build(&mut self) -> Result<(), String> {
+        self.letter_bank.clear();
+        if self.settings.mode == Mode::Blanks && self.settings.shared_letter_bank {
+            return self.build_shared_letter_bank();
+        }
         for phrase in self.phrases.iter_mut() {
-            phrase.build(self.blank_count);
+            match self.settings.mode {
+                Mode::Blanks => phrase.build_blanks(&self.settings)?,
+                Mode::Scramble => phrase.build_scramble(&self.settings.dictionary)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn build_shared_letter_bank(&mut self) -> Result<(), String> {
+        for _ in 0..TRY_COUNT_MAX {
+            for phrase in self.phrases.iter_mut() {
+                phrase.build_blanks(&self.settings)?;
+            }
+            if self.count_shared_solutions(2)? == 1 {
+                self.fill_letter_bank();
+                return Ok(());
+            }
         }
+        Err(format!("Unable to blank letters so that the shared letter bank fits back in just one way after {} tries.", TRY_COUNT_MAX))
+    }
+
+    fn fill_letter_bank(&mut self) {
+        self.letter_bank = self.phrases.iter().flat_map(|phrase| phrase.missing_letters.iter().cloned()).collect();
+        self.letter_bank.shuffle(&mut rng());
     }
 
-    pub fn try_jumbles(phrases: &[&str], blank_count: usize, try_count_max: usize) {
+    /// Instead of build, blank out exactly these letters of each phrase, in the order of
+    /// get_phrases. With a shared letter bank, the missing letters are pooled into it whether or
+    /// not they fit back in just one way.
+    pub fn set_blank_indexes(&mut self, blank_indexes: &[Vec<usize>]) -> Result<(), String> {
+        if blank_indexes.len() != self.phrases.len() {
+            return Err(format!("The puzzle has {} phrases but there are {} sets of blanks.", self.phrases.len(), blank_indexes.len()));
+        }
+        for (phrase, blank_indexes) in self.phrases.iter_mut().zip(blank_indexes.iter()) {
+            phrase.set_blank_indexes(blank_indexes)?;
+        }
+        self.letter_bank.clear();
+        if self.settings.shared_letter_bank {
+            self.fill_letter_bank();
+        }
+        Ok(())
+    }
+
+    /// Like Phrase::count_solutions, but with the missing letters from every phrase pooled so that
+    /// any of them can go in any blank.
+    pub fn count_shared_solutions(&self, limit: usize) -> Result<usize, String> {
+        count_shared_solutions(&self.phrases, &self.settings.dictionary, limit)
+    }
+
+    pub fn try_jumbles(phrases: &[&str], settings: &Settings, try_count_max: usize) -> Result<(), String> {
         for _ in 0..try_count_max {
-            let mut puzzle = Puzzle::new(phrases, blank_count);
-            puzzle.build();
+            let mut puzzle = Puzzle::new(phrases, settings)?;
+            puzzle.build()?;
             puzzle.print(true);
         }
+        Ok(())
     }
 
     pub fn print(&self, show_phrase: bool) {
         println!("\n=============================================================================\n");
-        for phrase in self.phrases.iter() {
+        println!("{}", self.get_string(show_phrase));
+        println!("\n-----------------------------------------------------------------------------\n");
+    }
+
+    pub fn get_string(&self, show_phrase: bool) -> String {
+        let mut s = "".to_string();
+        if !self.letter_bank.is_empty() {
+            s.push_str(&format!("Letter bank: {}\n\n", self.letter_bank.iter().join(" ")));
+        }
+        let mut category = None;
+        for (index, phrase) in self.phrases.iter().enumerate() {
+            if phrase.category.is_some() && phrase.category != category {
+                category = phrase.category.clone();
+                s.push_str(&format!("{}\n\n", category.as_ref().unwrap()));
+            }
+            let mut lines = vec![];
+            if show_phrase {
+                lines.push(phrase.phrase.clone());
+            }
+            if !phrase.missing_letters.is_empty() && self.letter_bank.is_empty() {
+                lines.push(phrase.missing_letters.iter().join(" "));
+            }
+            lines.push(phrase.jumble.clone());
+            if !phrase.circled_indexes.is_empty() {
+                lines.push(phrase.get_circles_string());
+            }
+            if let Some(hint) = &phrase.hint {
+                lines.push(format!("Hint: {}", hint));
+            }
+            // Numbered like the answer key, with the rest of the item's lines indented to match.
+            let number = format!("{}. ", index + 1);
+            for (line_index, line) in lines.iter().enumerate() {
+                let prefix = if line_index == 0 { number.clone() } else { " ".repeat(number.len()) };
+                s.push_str(&format!("{}{}\n", prefix, line));
+            }
+            s.push('\n');
+        }
+        if let Some(riddle) = &self.riddle {
+            s.push_str(&format!("{}\n\n{}\n", riddle.question, riddle.get_pattern_string()));
             if show_phrase {
-                println!("{}", phrase.phrase);
+                s.push_str(&format!("{}\n", riddle.answer));
             }
-            let missing_chars = phrase.missing_chars.iter().join(" ");
-            println!("{}", missing_chars);
-            println!("{}\n", phrase.jumble);
         }
-        println!("\n-----------------------------------------------------------------------------\n");
+        s
+    }
+
+    pub fn get_phrases(&self) -> &Vec<Phrase> {
+        &self.phrases
+    }
+
+    pub fn get_settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// All of the missing letters, shuffled, if the puzzle uses a shared letter bank.
+    pub fn get_letter_bank(&self) -> &Vec<String> {
+        &self.letter_bank
+    }
+
+    pub fn get_riddle(&self) -> Option<&Riddle> {
+        self.riddle.as_ref()
+    }
+
+    /// Check a student's answers, one per phrase in the order they're shown, written out in full.
+    pub fn check(&self, answers: &[&str]) -> Result<Check, String> {
+        check::check(self, answers)
+    }
+
+    pub fn get_answer_string(&self) -> String {
+        self.phrases.iter()
+            .enumerate()
+            .map(|(index, phrase)| format!("{}. {}\n", index + 1, phrase.phrase))
+            .chain(self.riddle.iter().map(|riddle| format!("{}\n", riddle.answer)))
+            .join("")
     }
 }
 
 impl Phrase {
-    pub fn new(phrase: &str) -> Self {
-        let mut char_indexes = vec![];
-        for (i, c) in phrase.chars().enumerate() {
-            if c.is_alphabetic() {
-                char_indexes.push(i);
-            }
+    /// Leading and trailing whitespace is trimmed, since it would be part of the answer but can't
+    /// be seen in the puzzle.
+    pub fn new(phrase: &str) -> Result<Self, String> {
+        let phrase = phrase.trim();
+        let graphemes = get_graphemes(phrase);
+        let letter_indexes = graphemes.iter()
+            .enumerate()
+            .filter(|(_, grapheme)| is_letter(grapheme))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        // The missing letters have to include at least two different ones, or there's nothing to
+        // unscramble.
+        let distinct_letter_count = letter_indexes.iter().map(|index| &graphemes[*index]).unique().count();
+        if distinct_letter_count < 2 {
+            return Err(format!("\"{}\" needs at least two different letters to make a jumble.", phrase));
         }
-        Self {
+        Ok(Self {
             phrase: phrase.to_string(),
+            graphemes,
             jumble: "".to_string(),
-            missing_chars: vec![],
-            char_indexes,
-        }
+            missing_letters: vec![],
+            letter_indexes,
+            blank_indexes: vec![],
+            circled_indexes: vec![],
+            hint: None,
+            category: None,
+            blank_count: None,
+        })
     }
 
-    fn build(&mut self, blank_count: usize) {
-        let try_count_for_error = 100;
+    fn build_blanks(&mut self, settings: &Settings) -> Result<(), String> {
+        let try_count_for_error = TRY_COUNT_MAX;
         let mut try_count = 0;
-        let blank_count = blank_count.min(self.phrase.len() - 1);
+        let weights = self.get_blank_weights(settings);
+        // Each group is a set of letters that may be blanked and how many of them to blank. A
+        // blank count from the phrase file wins over the settings.
+        let groups = match (self.blank_count, settings.blank_percent) {
+            (None, Some(percent)) if percent > 100 => return Err(format!("The blank percentage must be from 0 to 100, not {}.", percent)),
+            (None, Some(percent)) => self.get_word_blank_groups(&weights, percent),
+            (blank_count, _) => {
+                // Leave at least one letter showing.
+                let blank_count = blank_count.unwrap_or(settings.blank_count).min(weights.len()).min(self.letter_indexes.len() - 1);
+                vec![(weights, blank_count)]
+            },
+        };
+        let blank_count = groups.iter().map(|(_, blank_count)| blank_count).sum::<usize>();
+        if blank_count < 2 {
+            return Err(format!("\"{}\" needs at least two blanks but has {}.", self.phrase, blank_count));
+        }
         loop {
-            self.char_indexes.shuffle(&mut thread_rng());
-            self.jumble = "".to_string();
-            self.missing_chars.clear();
-            for (i, c) in self.phrase.chars().enumerate() {
-                if self.char_indexes[..blank_count].contains(&i) {
-                    self.jumble.push('_');
-                    self.missing_chars.push(c);
-                } else {
-                    self.jumble.push(c);
-                }
+            self.blank_indexes.clear();
+            for (weights, blank_count) in groups.iter() {
+                let chosen = weights.choose_multiple_weighted(&mut rng(), *blank_count, |(_, weight)| *weight)
+                    .map_err(|e| e.to_string())?;
+                self.blank_indexes.extend(chosen.map(|(index, _)| *index));
             }
-            let mut test_missing_chars = self.missing_chars.clone();
-            test_missing_chars.sort();
-            test_missing_chars.dedup();
-            if test_missing_chars.len() >= 2 {
-                self.missing_chars.shuffle(&mut thread_rng());
-                return;
+            self.blank_indexes.sort();
+            self.apply_blanks();
+            let distinct_missing_count = self.missing_letters.iter().unique().count();
+            let is_unique = !settings.require_unique_solution || self.count_solutions(&settings.dictionary, 2)? == 1;
+            if distinct_missing_count >= 2 && is_unique {
+                self.missing_letters.shuffle(&mut rng());
+                return Ok(());
             }
             try_count += 1;
             if try_count >= try_count_for_error {
-                panic!();
+                return Err(if settings.require_unique_solution {
+                    format!("Unable to blank letters in \"{}\" so that there's only one answer after {} tries.", self.phrase, try_count)
+                } else {
+                    format!("Unable to blank two different letters in \"{}\" after {} tries.", self.phrase, try_count)
+                });
             }
        }
     }
+
+    // For a blank percentage, one group per word with that share of the word's letters, rounded,
+    // and at least one letter left showing. If that comes to fewer than two blanks in all, the
+    // words with the most letters left to blank make up the difference.
+    fn get_word_blank_groups(&self, weights: &[(usize, f32)], percent: usize) -> Vec<(Vec<(usize, f32)>, usize)> {
+        let mut groups = self.get_word_ranges().into_iter()
+            .map(|(start, end)| {
+                let word_weights = weights.iter().filter(|(index, _)| (start..end).contains(index)).cloned().collect::<Vec<_>>();
+                let blank_count_max = word_weights.len().min(end - start - 1);
+                let blank_count = (((end - start) * percent + 50) / 100).min(blank_count_max);
+                (word_weights, blank_count, blank_count_max)
+            })
+            .collect::<Vec<_>>();
+        while groups.iter().map(|(_, blank_count, _)| blank_count).sum::<usize>() < 2 {
+            match groups.iter_mut().filter(|(_, blank_count, blank_count_max)| blank_count < blank_count_max)
+                .max_by_key(|(_, blank_count, blank_count_max)| *blank_count_max - *blank_count) {
+                Some((_, blank_count, _)) => *blank_count += 1,
+                None => break,
+            }
+        }
+        groups.into_iter().map(|(word_weights, blank_count, _)| (word_weights, blank_count)).collect()
+    }
+
+    /// Blank out exactly these letters, as for a cloze exercise where the blanks are chosen by
+    /// where the vocabulary word is rather than at random. The missing letters stay in order.
+    pub fn set_blank_indexes(&mut self, blank_indexes: &[usize]) -> Result<(), String> {
+        if let Some(index) = blank_indexes.iter().find(|index| !self.letter_indexes.contains(index)) {
+            return Err(format!("Position {} in \"{}\" isn't a letter that can be blanked.", index, self.phrase));
+        }
+        self.blank_indexes = blank_indexes.iter().cloned().unique().sorted().collect();
+        self.apply_blanks();
+        Ok(())
+    }
+
+    fn apply_blanks(&mut self) {
+        self.jumble = "".to_string();
+        self.missing_letters.clear();
+        for (i, grapheme) in self.graphemes.iter().enumerate() {
+            if self.blank_indexes.contains(&i) {
+                self.jumble.push('_');
+                self.missing_letters.push(grapheme.clone());
+            } else {
+                self.jumble.push_str(grapheme);
+            }
+        }
+    }
+
+    /// The start and end (exclusive) grapheme indexes of each word in the phrase.
+    pub fn get_word_ranges(&self) -> Vec<(usize, usize)> {
+        get_word_ranges(&self.graphemes)
+    }
+
+    // The indexes of the letters that may be blanked, each with how likely it is to be chosen
+    // relative to the others.
+    fn get_blank_weights(&self, settings: &Settings) -> Vec<(usize, f32)> {
+        let graphemes = &self.graphemes;
+        let is_consonant = |index: usize| graphemes.get(index).is_some_and(|grapheme| is_letter(grapheme) && !is_vowel(grapheme));
+        let mut weights = vec![];
+        for (start, end) in get_word_ranges(graphemes) {
+            for index in start..end {
+                let weight = match settings.difficulty {
+                    Difficulty::Easy if index == start => continue,
+                    Difficulty::Easy => if is_vowel(&graphemes[index]) { 3.0 } else { 1.0 },
+                    Difficulty::Medium => 1.0,
+                    Difficulty::Hard => {
+                        let in_cluster = is_consonant(index) && ((index > start && is_consonant(index - 1)) || is_consonant(index + 1));
+                        let ambiguous = is_ambiguous(&graphemes[start..end], index - start, &settings.dictionary);
+                        1.0 + if in_cluster { 2.0 } else { 0.0 } + if ambiguous { 2.0 } else { 0.0 }
+                    },
+                };
+                weights.push((index, weight));
+            }
+        }
+        weights
+    }
+
+    pub fn get_phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// The phrase split into grapheme clusters, the characters a reader sees. The indexes of the
+    /// blanks and circled letters are positions in this list.
+    pub fn get_graphemes(&self) -> &Vec<String> {
+        &self.graphemes
+    }
+
+    pub fn get_hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+
+    pub fn get_category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    pub fn get_jumble(&self) -> &str {
+        &self.jumble
+    }
+
+    pub fn get_missing_letters(&self) -> &Vec<String> {
+        &self.missing_letters
+    }
+
+    /// The grapheme positions in the phrase that are blank, in order.
+    pub fn get_blank_indexes(&self) -> &Vec<usize> {
+        &self.blank_indexes
+    }
+
+    /// The grapheme positions in the phrase whose letters are circled, in order.
+    pub fn get_circled_indexes(&self) -> &Vec<usize> {
+        &self.circled_indexes
+    }
+
+    /// A box for each letter of the unscrambled phrase, with circles for the circled letters.
+    pub fn get_circles_string(&self) -> String {
+        self.graphemes.iter()
+            .enumerate()
+            .map(|(index, grapheme)| if self.circled_indexes.contains(&index) { "( )".to_string() } else if is_letter(grapheme) { "[ ]".to_string() } else { grapheme.clone() })
+            .join(" ")
+    }
+
+    /// The number of ways the missing letters can go back in the blanks so that every word with
+    /// a blank is in the dictionary, counting up to limit. The phrase itself is one of them as
+    /// long as its words are in the dictionary. This is an error if there are too many ways to
+    /// try to count them all.
+    pub fn count_solutions(&self, dictionary: &Dictionary, limit: usize) -> Result<usize, String> {
+        count_solutions(&self.graphemes, &self.blank_indexes, &self.missing_letters, dictionary, limit)
+    }
+
+    fn build_scramble(&mut self, dictionary: &Dictionary) -> Result<(), String> {
+        self.missing_letters.clear();
+        self.blank_indexes.clear();
+        let mut graphemes = self.graphemes.clone();
+        let mut is_scrambled = false;
+        // Each run of letters is scrambled on its own so that the spaces and punctuation between
+        // words stay where they are.
+        // Each word is put back with the same number of graphemes, so the ranges stay correct.
+        for (start, end) in get_word_ranges(&graphemes) {
+            if let Some(scrambled) = scramble_word(&graphemes[start..end], dictionary)? {
+                graphemes.splice(start..end, scrambled);
+                is_scrambled = true;
+            }
+        }
+        if !is_scrambled {
+            return Err(format!("\"{}\" has no word with two different letters to scramble.", self.phrase));
+        }
+        self.jumble = graphemes.concat();
+        Ok(())
+    }
+}
+
+// The blank indexes must be in order. Case is ignored, so a "T" and a "t" in the missing letters
+// are the same letter and swapping them isn't another solution.
+fn count_solutions(graphemes: &[String], blank_indexes: &[usize], missing_letters: &[String], dictionary: &Dictionary, limit: usize) -> Result<usize, String> {
+    let mut graphemes = graphemes.iter().map(|grapheme| grapheme.to_lowercase()).collect::<Vec<_>>();
+    let word_ranges = get_word_ranges(&graphemes);
+    // For each blank, the start of the word it's in, how far along the word is known once the
+    // blank is filled (up to the next blank in the word or the end of the word), and whether
+    // that's the whole word.
+    let blanks = blank_indexes.iter()
+        .enumerate()
+        .map(|(index, blank_index)| {
+            let (start, end) = *word_ranges.iter().find(|(start, end)| blank_index >= start && blank_index < end).unwrap();
+            match blank_indexes.get(index + 1).filter(|next| **next < end) {
+                Some(next) => (*blank_index, start, *next, false),
+                None => (*blank_index, start, end, true),
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut remaining: BTreeMap<String, usize> = BTreeMap::new();
+    for letter in missing_letters.iter() {
+        *remaining.entry(letter.to_lowercase()).or_insert(0) += 1;
+    }
+    let mut count = 0;
+    let mut node_count = 0;
+    count_solutions_from(&blanks, &mut graphemes, &mut remaining, dictionary, limit, &mut count, &mut node_count)?;
+    Ok(count)
+}
+
+/// The number of ways the missing letters from all of the phrases, pooled into one letter bank, can
+/// go back in the blanks so that every word with a blank is in the dictionary, counting up to
+/// limit. Like Phrase::count_solutions, this is an error if there are too many ways to try.
+pub fn count_shared_solutions(phrases: &[Phrase], dictionary: &Dictionary, limit: usize) -> Result<usize, String> {
+    let mut graphemes = vec![];
+    let mut blank_indexes = vec![];
+    let mut missing_letters = vec![];
+    for phrase in phrases.iter() {
+        let offset = graphemes.len();
+        graphemes.extend(phrase.graphemes.iter().cloned());
+        // A line break keeps the last word of one phrase apart from the first word of the next.
+        graphemes.push("\n".to_string());
+        blank_indexes.extend(phrase.blank_indexes.iter().map(|index| index + offset));
+        missing_letters.extend(phrase.missing_letters.iter().cloned());
+    }
+    count_solutions(&graphemes, &blank_indexes, &missing_letters, dictionary, limit)
+}
+
+// Try each distinct letter that's left in the next blank, backing out as soon as the start of a
+// word doesn't begin any word in the dictionary, or a complete word isn't in it.
+fn count_solutions_from(blanks: &[(usize, usize, usize, bool)], graphemes: &mut Vec<String>, remaining: &mut BTreeMap<String, usize>, dictionary: &Dictionary, limit: usize, count: &mut usize, node_count: &mut usize) -> Result<(), String> {
+    if blanks.is_empty() {
+        *count += 1;
+        return Ok(());
+    }
+    *node_count += 1;
+    if *node_count > SEARCH_NODE_MAX {
+        return Err(format!("Gave up counting the ways the letters fit back in after trying {} partial answers. Try fewer blanks.", SEARCH_NODE_MAX));
+    }
+    let (blank_index, start, known_end, is_whole_word) = blanks[0];
+    let letters = remaining.iter().filter(|(_, n)| **n > 0).map(|(letter, _)| letter.clone()).collect::<Vec<_>>();
+    for letter in letters {
+        if *count >= limit {
+            return Ok(());
+        }
+        graphemes[blank_index] = letter.clone();
+        let known = graphemes[start..known_end].concat();
+        let is_possible = if is_whole_word { dictionary.contains(&known) } else { dictionary.contains_prefix(&known) };
+        if !is_possible {
+            continue;
+        }
+        *remaining.get_mut(&letter).unwrap() -= 1;
+        let result = count_solutions_from(&blanks[1..], graphemes, remaining, dictionary, limit, count, node_count);
+        *remaining.get_mut(&letter).unwrap() += 1;
+        result?;
+    }
+    Ok(())
+}
+
+// Whether some other letter in that spot would also make a dictionary word, as with the "c" in
+// "cat", which could be a "b" or an "h".
+fn is_ambiguous(word: &[String], index: usize, dictionary: &Dictionary) -> bool {
+    let mut word = word.to_vec();
+    let letter = word[index].to_lowercase();
+    ('a'..='z')
+        .map(|other| other.to_string())
+        .filter(|other| *other != letter)
+        .any(|other| {
+            word[index] = other;
+            dictionary.contains(&word.concat())
+        })
+}
+
+/// Split text into grapheme clusters so that a letter with a combining accent counts as one
+/// letter and stays in one piece.
+pub fn get_graphemes(s: &str) -> Vec<String> {
+    s.graphemes(true).map(|grapheme| grapheme.to_string()).collect()
+}
+
+/// Whether a grapheme cluster is a letter, possibly with accents.
+pub fn is_letter(grapheme: &str) -> bool {
+    grapheme.chars().next().is_some_and(|c| c.is_alphabetic())
+}
+
+fn is_vowel(grapheme: &str) -> bool {
+    grapheme.chars().next().is_some_and(|c| c.to_lowercase().any(|c| VOWELS.contains(c)))
+}
+
+fn is_upper(grapheme: &str) -> bool {
+    grapheme.chars().next().is_some_and(|c| c.is_uppercase())
+}
+
+// Each run of letters in a phrase.
+fn get_words(phrase: &str) -> Vec<String> {
+    let graphemes = get_graphemes(phrase);
+    get_word_ranges(&graphemes).iter().map(|(start, end)| graphemes[*start..*end].concat()).collect()
+}
+
+// The start and end (exclusive) grapheme indexes of each run of letters.
+pub(super) fn get_word_ranges(graphemes: &[String]) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    let mut start = None;
+    for (index, grapheme) in graphemes.iter().enumerate() {
+        match (is_letter(grapheme), start) {
+            (true, None) => start = Some(index),
+            (false, Some(word_start)) => {
+                ranges.push((word_start, index));
+                start = None;
+            },
+            _ => {},
+        }
+    }
+    if let Some(word_start) = start {
+        ranges.push((word_start, graphemes.len()));
+    }
+    ranges
+}
+
+/// Shuffle the graphemes of a word until they're in a different order and don't spell a word in
+/// the dictionary. Capital letters stay in the same positions, so "Paris" might become "Sirap".
+/// The result has the same number of graphemes as the word. Returns None for a word like "a" or
+/// "zz" that can't be changed by shuffling.
+pub fn scramble_word(graphemes: &[String], dictionary: &Dictionary) -> Result<Option<Vec<String>>, String> {
+    let letters = graphemes.iter().map(|grapheme| change_case(grapheme, false)).collect::<Vec<_>>();
+    if letters.iter().unique().count() < 2 {
+        return Ok(None);
+    }
+    let mut shuffled = letters.clone();
+    for _ in 0..TRY_COUNT_MAX {
+        shuffled.shuffle(&mut rng());
+        if shuffled == letters || dictionary.contains(&shuffled.concat()) {
+            continue;
+        }
+        let scrambled = shuffled.iter()
+            .zip(graphemes.iter())
+            .map(|(letter, original)| if is_upper(original) { change_case(letter, true) } else { letter.clone() })
+            .collect();
+        return Ok(Some(scrambled));
+    }
+    Err(format!("Unable to scramble \"{}\" into something that isn't a word after {} tries.", graphemes.concat(), TRY_COUNT_MAX))
+}
+
+// The grapheme in upper or lower case, unless that would make it more than one grapheme, as "ß"
+// becomes "SS", in which case it stays as it is.
+fn change_case(grapheme: &str, upper: bool) -> String {
+    let changed = if upper { grapheme.to_uppercase() } else { grapheme.to_lowercase() };
+    if changed.graphemes(true).count() == 1 { changed } else { grapheme.to_string() }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Blanks,
+            blank_count: 2,
+            blank_percent: None,
+            difficulty: Difficulty::Medium,
+            dictionary: Dictionary::built_in(),
+            require_unique_solution: false,
+            shared_letter_bank: false,
+            riddle_word_count: 4,
+        }
+    }
 }
 
 pub fn main() {
-    let phrases = word_list::WORDS_1;
-    let blank_count = 2;
-    let try_count_max = 5;
-    Puzzle::try_jumbles(&phrases, blank_count, try_count_max);
+    let text = "phrase,hint,category,blanks\n\
+        vanish,Disappear,Verbs,3\n\
+        swoop,What a hawk does,Verbs,\n\
+        label,A tag with a name on it,Nouns,\n\
+        the big red barn,Where the cows sleep,Phrases,5\n";
+    let list = PhraseList::parse(text, word_list::FileFormat::Csv).unwrap();
+    let mut puzzle = Puzzle::from_phrase_list(&list, &Settings::default()).unwrap();
+    puzzle.build().unwrap();
+    puzzle.print(false);
+    println!("{}", puzzle.get_answer_string());
 }
\ No newline at end of file
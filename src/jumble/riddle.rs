@@ -0,0 +1,127 @@
+use crate::*;
+use crate::random::rng;
+use super::puzzle::{get_graphemes, is_letter};
+
+use rand::seq::SliceRandom;
+use std::collections::BTreeMap;
+
+const TRY_COUNT_MAX: usize = 1_000;
+const WORD_LENGTH_MIN: usize = 3;
+
+/// The final question of a classic newspaper jumble. The circled letters of the unscrambled words
+/// are the letters of its answer, which is usually a pun.
+#[derive(Clone, Debug)]
+pub struct Riddle {
+    pub question: String,
+    pub answer: String,
+}
+
+impl Riddle {
+    pub fn new(question: &str, answer: &str) -> Result<Self, String> {
+        let riddle = Self {
+            question: question.trim().to_string(),
+            answer: answer.trim().to_string(),
+        };
+        if riddle.get_letters().is_empty() {
+            return Err(format!("The answer to \"{}\" has no letters.", riddle.question));
+        }
+        Ok(riddle)
+    }
+
+    /// The letters of the answer in lowercase, without spaces or punctuation.
+    pub fn get_letters(&self) -> Vec<String> {
+        get_graphemes(&self.answer.to_lowercase()).into_iter().filter(|grapheme| is_letter(grapheme)).collect()
+    }
+
+    /// The answer with a circle for each letter, keeping the spaces and punctuation so that the
+    /// solver can see how many words there are.
+    pub fn get_pattern_string(&self) -> String {
+        get_graphemes(&self.answer).into_iter()
+            .map(|grapheme| if is_letter(&grapheme) { "( )".to_string() } else if grapheme == " " { "  ".to_string() } else { grapheme })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Choose word_count words from the list and some letters in each to circle so that the circled
+/// letters are exactly the letters of the answer. Each word has at least one letter circled and
+/// at least one not circled. Returns the words in lowercase with the indexes of their circled
+/// letters, counted in graphemes as in a Phrase rather than in chars.
+pub fn choose_circled_words(answer_letters: &[String], words: &[&str], word_count: usize) -> Result<Vec<(String, Vec<usize>)>, String> {
+    let mut candidates = words.iter()
+        .map(|word| word.trim().to_lowercase())
+        .filter(|word| {
+            let graphemes = get_graphemes(word);
+            graphemes.len() >= WORD_LENGTH_MIN && graphemes.iter().all(|grapheme| is_letter(grapheme)) && graphemes.iter().unique().count() >= 2
+        })
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates.dedup();
+    if word_count == 0 || candidates.len() < word_count {
+        return Err(format!("Need {} words to choose from but the list has {} that can be scrambled.", word_count, candidates.len()));
+    }
+    if answer_letters.len() < word_count {
+        return Err(format!("The answer has {} letters, too few to circle at least one in each of {} words.", answer_letters.len(), word_count));
+    }
+    // Spread the answer's letters as evenly as possible over the words.
+    let mut circle_counts = (0..word_count)
+        .map(|index| (answer_letters.len() / word_count) + if index < answer_letters.len() % word_count { 1 } else { 0 })
+        .collect::<Vec<_>>();
+    for _ in 0..TRY_COUNT_MAX {
+        candidates.shuffle(&mut rng());
+        circle_counts.shuffle(&mut rng());
+        if let Some(chosen) = try_choose_circled_words(answer_letters, &candidates, &circle_counts) {
+            return Ok(chosen);
+        }
+    }
+    Err(format!("Unable to find {} words in the list whose letters can make \"{}\".", word_count, answer_letters.concat()))
+}
+
+fn try_choose_circled_words(answer_letters: &[String], candidates: &[String], circle_counts: &[usize]) -> Option<Vec<(String, Vec<usize>)>> {
+    let mut remaining: BTreeMap<String, usize> = BTreeMap::new();
+    for letter in answer_letters.iter() {
+        *remaining.entry(letter.clone()).or_insert(0) += 1;
+    }
+    let mut chosen: Vec<(String, Vec<usize>)> = vec![];
+    for circle_count in circle_counts.iter() {
+        let word = candidates.iter()
+            .filter(|word| !chosen.iter().any(|(chosen_word, _)| chosen_word == *word))
+            .filter(|word| get_graphemes(word).len() > *circle_count)
+            .find(|word| count_available(word, &remaining) >= *circle_count)?;
+        // Circle letters at random positions among those the answer still needs.
+        let mut positions = get_graphemes(word).into_iter().enumerate().collect::<Vec<_>>();
+        positions.shuffle(&mut rng());
+        let mut circled_indexes = vec![];
+        for (index, letter) in positions {
+            if circled_indexes.len() == *circle_count {
+                break;
+            }
+            if let Some(count) = remaining.get_mut(&letter).filter(|count| **count > 0) {
+                *count -= 1;
+                circled_indexes.push(index);
+            }
+        }
+        circled_indexes.sort();
+        chosen.push((word.clone(), circled_indexes));
+    }
+    Some(chosen)
+}
+
+// How many of the word's letters could go toward the remaining letters of the answer.
+fn count_available(word: &str, remaining: &BTreeMap<String, usize>) -> usize {
+    let mut word_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for letter in get_graphemes(word) {
+        *word_counts.entry(letter).or_insert(0) += 1;
+    }
+    word_counts.iter()
+        .map(|(letter, count)| (*count).min(remaining.get(letter).cloned().unwrap_or(0)))
+        .sum()
+}
+
+pub fn main() {
+    let riddle = Riddle::new("What did the baker say when the bread rose?", "Yeast of all").unwrap();
+    let mut puzzle = super::puzzle::Puzzle::new_classic(&riddle, &word_list::ALL_SECOND_GRADE, &super::puzzle::Settings::default()).unwrap();
+    puzzle.build().unwrap();
+    puzzle.print(false);
+    println!("{}", puzzle.get_answer_string());
+}
@@ -0,0 +1,135 @@
+use crate::*;
+use super::puzzle::{Mode, Phrase, Puzzle, Settings};
+use super::puzzle::{get_graphemes, is_letter};
+
+// Inline so that the file can be opened or printed on its own.
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; }
+h1 { text-align: center; }
+ol { padding-left: 2em; }
+li { margin-bottom: 1.5em; font-size: 1.4em; }
+.bank { border: 1px solid black; padding: 0.5em; margin-bottom: 1.5em; font-size: 1.4em; letter-spacing: 0.5em; }
+.category { font-weight: bold; font-size: 1.4em; margin-bottom: 0.5em; }
+.hint { font-size: 0.7em; font-style: italic; margin-top: 0.3em; }
+.missing { font-size: 0.8em; letter-spacing: 0.3em; margin-bottom: 0.3em; }
+.jumble { letter-spacing: 0.5em; margin-bottom: 0.3em; }
+.box, .circle, .letter, .gap { display: inline-block; width: 1.4em; height: 1.4em; line-height: 1.4em; margin: 0.1em; text-align: center; vertical-align: middle; }
+.box { border: 1px solid black; }
+.circle { border: 1px solid black; border-radius: 50%; }
+.riddle { margin-top: 2em; font-size: 1.4em; }
+.answer-key { page-break-before: always; }
+";
+
+/// A complete HTML page for printing: the numbered items with a box for each letter to fill in,
+/// then the answer key on a page of its own.
+pub fn get_html_string(puzzle: &Puzzle, title: &str) -> String {
+    let mut s = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n", escape(title), STYLE);
+    s.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+    let letter_bank = puzzle.get_letter_bank();
+    if !letter_bank.is_empty() {
+        s.push_str(&format!("<div class=\"bank\">{}</div>\n", escape(&letter_bank.iter().join(" "))));
+    }
+    s.push_str(&get_items_html(puzzle, false));
+    s.push_str("<div class=\"answer-key\">\n");
+    s.push_str(&format!("<h1>{} - Answer Key</h1>\n", escape(title)));
+    s.push_str(&get_items_html(puzzle, true));
+    s.push_str("</div>\n</body>\n</html>\n");
+    s
+}
+
+fn get_items_html(puzzle: &Puzzle, show_answers: bool) -> String {
+    let settings = puzzle.get_settings();
+    let mut s = "".to_string();
+    let mut category = None;
+    for (index, phrase) in puzzle.get_phrases().iter().enumerate() {
+        // Each category gets a heading and a list of its own, numbered on from the list before.
+        let is_new_category = phrase.get_category().is_some() && phrase.get_category() != category;
+        if index == 0 || is_new_category {
+            if index > 0 {
+                s.push_str("</ol>\n");
+            }
+            if is_new_category {
+                category = phrase.get_category();
+                s.push_str(&format!("<div class=\"category\">{}</div>\n", escape(category.unwrap())));
+            }
+            s.push_str(&format!("<ol start=\"{}\">\n", index + 1));
+        }
+        s.push_str("<li>\n");
+        match settings.mode {
+            Mode::Blanks => s.push_str(&get_blanks_html(phrase, settings, show_answers)),
+            Mode::Scramble => s.push_str(&get_scramble_html(phrase, show_answers)),
+        }
+        if let Some(hint) = phrase.get_hint() {
+            s.push_str(&format!("<div class=\"hint\">{}</div>\n", escape(hint)));
+        }
+        s.push_str("</li>\n");
+    }
+    if !puzzle.get_phrases().is_empty() {
+        s.push_str("</ol>\n");
+    }
+    if let Some(riddle) = puzzle.get_riddle() {
+        s.push_str(&format!("<div class=\"riddle\">\n<p>{}</p>\n<div>", escape(&riddle.question)));
+        for grapheme in get_graphemes(&riddle.answer) {
+            s.push_str(&get_cell_html(&grapheme, is_letter(&grapheme), "circle", show_answers));
+        }
+        s.push_str("</div>\n</div>\n");
+    }
+    s
+}
+
+// The phrase with a box for each blank. Without a shared letter bank, each item lists its own
+// missing letters above it.
+fn get_blanks_html(phrase: &Phrase, settings: &Settings, show_answers: bool) -> String {
+    let mut s = "".to_string();
+    if !settings.shared_letter_bank && !show_answers {
+        s.push_str(&format!("<div class=\"missing\">{}</div>\n", escape(&phrase.get_missing_letters().iter().join(" "))));
+    }
+    s.push_str("<div>");
+    for (index, grapheme) in phrase.get_graphemes().iter().enumerate() {
+        s.push_str(&get_cell_html(grapheme, phrase.get_blank_indexes().contains(&index), "box", show_answers));
+    }
+    s.push_str("</div>\n");
+    s
+}
+
+// The scrambled letters with a box below for each letter of the answer, or a circle for the
+// letters that go toward a riddle.
+fn get_scramble_html(phrase: &Phrase, show_answers: bool) -> String {
+    let mut s = format!("<div class=\"jumble\">{}</div>\n<div>", escape(phrase.get_jumble()));
+    for (index, grapheme) in phrase.get_graphemes().iter().enumerate() {
+        let class = if phrase.get_circled_indexes().contains(&index) { "circle" } else { "box" };
+        s.push_str(&get_cell_html(grapheme, is_letter(grapheme), class, show_answers));
+    }
+    s.push_str("</div>\n");
+    s
+}
+
+// A letter to be filled in is drawn as an empty box or circle, or with the letter inside it in
+// the answer key. Anything else is shown as it is.
+fn get_cell_html(grapheme: &str, is_fill_in: bool, fill_in_class: &str, show_answers: bool) -> String {
+    if is_fill_in {
+        let content = if show_answers { escape(grapheme) } else { "".to_string() };
+        format!("<span class=\"{}\">{}</span>", fill_in_class, content)
+    } else if grapheme == " " {
+        "<span class=\"gap\"></span>".to_string()
+    } else {
+        format!("<span class=\"letter\">{}</span>", escape(grapheme))
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn main() {
+    let settings = Settings {
+        shared_letter_bank: true,
+        ..Default::default()
+    };
+    let mut puzzle = Puzzle::new(&word_list::WORDS_1, &settings).unwrap();
+    puzzle.build().unwrap();
+    println!("{}", get_html_string(&puzzle, "Jumble"));
+}
@@ -1,10 +1,12 @@
-#![feature(drain_filter)]
-
 pub use itertools::Itertools;
 pub use util::*;
 pub use util::format::fc;
 
+pub mod book;
+pub mod cli;
+pub mod grid;
 pub mod jumble;
+pub mod random;
 pub mod word_list;
 pub mod word_search;
 
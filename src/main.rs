@@ -1,11 +1,8 @@
 use word_games::*;
 
 fn main() {
-    println!("\nword_games start\n");
-
-    // word_search::puzzle::main();
-    // word_search::word_list::make_random_sublist(10, &word_search::word_list::ALL_SECOND_GRADE);
-    jumble::puzzle::main();
-
-    println!("\nword_games done\n");
+    if let Err(message) = cli::run(std::env::args_os()) {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,40 @@
+use rand::{Error, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cell::RefCell;
+
+thread_local! {
+    static RNG: RefCell<ChaCha8Rng> = RefCell::new(ChaCha8Rng::from_entropy());
+}
+
+/// A handle to the crate's per-thread random number generator. It's used everywhere that used to
+/// call rand::thread_rng() so that a whole puzzle can be reproduced from a single seed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GameRng;
+
+pub fn rng() -> GameRng {
+    GameRng
+}
+
+/// Reseed the generator for the current thread. Everything generated afterward on this thread is
+/// repeatable given the same seed and the same inputs.
+pub fn set_seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = ChaCha8Rng::seed_from_u64(seed));
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        RNG.with(|rng| rng.borrow_mut().next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        RNG.with(|rng| rng.borrow_mut().next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        RNG.with(|rng| rng.borrow_mut().fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        RNG.with(|rng| rng.borrow_mut().try_fill_bytes(dest))
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WordEntry {
+    pub word: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grade: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_of_speech: Option<PartOfSpeech>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+    Pronoun,
+    Preposition,
+    Conjunction,
+    Interjection,
+}
+
+impl WordEntry {
+    pub fn new(word: &str) -> Self {
+        Self {
+            word: word.trim().to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|entry_tag| entry_tag.eq_ignore_ascii_case(tag.trim()))
+    }
+
+    /// The definition followed by the letter count, for puzzles that show clues instead of the
+    /// words themselves.
+    pub fn get_clue(&self) -> Option<String> {
+        self.definition.as_ref().map(|definition| format!("{} ({} letters)", definition, self.word.chars().filter(|c| c.is_alphabetic()).count()))
+    }
+}
+
+impl From<&str> for WordEntry {
+    fn from(word: &str) -> Self {
+        Self::new(word)
+    }
+}
+
+impl PartOfSpeech {
+    fn get_name(&self) -> &str {
+        match self {
+            PartOfSpeech::Noun => "noun",
+            PartOfSpeech::Verb => "verb",
+            PartOfSpeech::Adjective => "adjective",
+            PartOfSpeech::Adverb => "adverb",
+            PartOfSpeech::Pronoun => "pronoun",
+            PartOfSpeech::Preposition => "preposition",
+            PartOfSpeech::Conjunction => "conjunction",
+            PartOfSpeech::Interjection => "interjection",
+        }
+    }
+}
+
+impl FromStr for PartOfSpeech {
+    type Err = String;
+
+    // Accept the usual dictionary abbreviations as well as the full names.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().trim_end_matches('.').to_lowercase().as_str() {
+            "noun" | "n" => Ok(PartOfSpeech::Noun),
+            "verb" | "v" => Ok(PartOfSpeech::Verb),
+            "adjective" | "adj" => Ok(PartOfSpeech::Adjective),
+            "adverb" | "adv" => Ok(PartOfSpeech::Adverb),
+            "pronoun" | "pron" => Ok(PartOfSpeech::Pronoun),
+            "preposition" | "prep" => Ok(PartOfSpeech::Preposition),
+            "conjunction" | "conj" => Ok(PartOfSpeech::Conjunction),
+            "interjection" | "interj" => Ok(PartOfSpeech::Interjection),
+            _ => Err(format!("\"{}\" is not a part of speech.", s.trim())),
+        }
+    }
+}
+
+impl TryFrom<String> for PartOfSpeech {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<PartOfSpeech> for String {
+    fn from(part_of_speech: PartOfSpeech) -> Self {
+        part_of_speech.get_name().to_string()
+    }
+}
+
+impl Display for PartOfSpeech {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.get_name())
+    }
+}
@@ -0,0 +1,151 @@
+use crate::*;
+use super::list::WordList;
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintKind {
+    Duplicate,
+    Contained,
+    ReversedCollision,
+    NonAlphabetic,
+    TooLong,
+}
+
+#[derive(Clone, Debug)]
+pub struct LintIssue {
+    pub severity: Severity,
+    pub kind: LintKind,
+    pub words: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct LintSettings {
+    /// Longer words can't fit in the grid.
+    pub length_max: usize,
+    /// Phrases with spaces are fine for jumbles but not for word searches.
+    pub allow_spaces: bool,
+}
+
+/// Check a list of words for problems that make puzzles ambiguous or impossible to build. The
+/// issues come back with the most severe first.
+pub fn lint<S: AsRef<str>>(words: &[S], settings: &LintSettings) -> Vec<LintIssue> {
+    let words = words.iter().map(|word| word.as_ref().trim().to_lowercase()).collect::<Vec<_>>();
+    let mut issues = vec![];
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for word in words.iter() {
+        *counts.entry(word.as_str()).or_insert(0) += 1;
+    }
+    for (word, count) in counts.iter().filter(|(_, count)| **count > 1) {
+        issues.push(LintIssue::new(Severity::Error, LintKind::Duplicate, &[word],
+            format!("\"{}\" appears {} times.", word, count)));
+    }
+    let distinct_words = counts.keys().cloned().collect::<Vec<_>>();
+
+    for word in distinct_words.iter() {
+        let bad_chars = word.chars()
+            .filter(|c| !(c.is_alphabetic() || settings.allow_spaces && *c == ' '))
+            .collect::<String>();
+        if !bad_chars.is_empty() {
+            issues.push(LintIssue::new(Severity::Error, LintKind::NonAlphabetic, &[word],
+                format!("\"{}\" has characters that aren't letters: \"{}\".", word, bad_chars)));
+        }
+        let length = word.chars().filter(|c| *c != ' ').count();
+        if length > settings.length_max {
+            issues.push(LintIssue::new(Severity::Error, LintKind::TooLong, &[word],
+                format!("\"{}\" has {} letters but the most that fit is {}.", word, length, settings.length_max)));
+        }
+    }
+
+    // A short word inside a longer one can be found in two places, and so can a word whose reversal
+    // is inside another since word searches can run backward.
+    for (index, word) in distinct_words.iter().enumerate() {
+        let reversed = word.chars().rev().collect::<String>();
+        for other in distinct_words.iter().skip(index + 1) {
+            let (shorter, longer) = if word.len() <= other.len() { (*word, *other) } else { (*other, *word) };
+            if longer.contains(shorter) {
+                issues.push(LintIssue::new(Severity::Warning, LintKind::Contained, &[shorter, longer],
+                    format!("\"{}\" is inside \"{}\".", shorter, longer)));
+            } else if reversed == *other {
+                issues.push(LintIssue::new(Severity::Warning, LintKind::ReversedCollision, &[word, other],
+                    format!("\"{}\" is \"{}\" spelled backward.", word, other)));
+            } else {
+                let shorter_reversed = shorter.chars().rev().collect::<String>();
+                if longer.contains(&shorter_reversed) && shorter_reversed != shorter {
+                    issues.push(LintIssue::new(Severity::Warning, LintKind::ReversedCollision, &[shorter, longer],
+                        format!("\"{}\" spelled backward is inside \"{}\".", shorter, longer)));
+                }
+            }
+        }
+    }
+
+    issues.sort_by(|a, b| a.severity.cmp(&b.severity).reverse());
+    issues
+}
+
+/// Lint a word list, including any duplicates that were dropped when it was loaded.
+pub fn lint_list(list: &WordList, settings: &LintSettings) -> Vec<LintIssue> {
+    let mut words = list.get_words();
+    words.extend(list.get_removed_duplicates().iter().cloned());
+    lint(&words, settings)
+}
+
+impl LintIssue {
+    fn new(severity: Severity, kind: LintKind, words: &[&str], message: String) -> Self {
+        Self {
+            severity,
+            kind,
+            words: words.iter().map(|word| word.to_string()).collect(),
+            message,
+        }
+    }
+}
+
+impl Default for LintSettings {
+    fn default() -> Self {
+        Self {
+            length_max: word_search::Puzzle::get_word_length_max(),
+            allow_spaces: false,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Display for LintKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let name = match self {
+            LintKind::Duplicate => "duplicate",
+            LintKind::Contained => "contained",
+            LintKind::ReversedCollision => "reversed",
+            LintKind::NonAlphabetic => "non-alphabetic",
+            LintKind::TooLong => "too-long",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{} [{}]: {}", self.severity, self.kind, self.message)
+    }
+}
@@ -0,0 +1,336 @@
+use crate::*;
+use super::entry::{PartOfSpeech, WordEntry};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+const COMMENT_CHAR: char = '#';
+const CSV_HEADERS: [&str; 6] = ["word", "grade", "definition", "example", "part_of_speech", "tags"];
+const CSV_TAG_SEPARATOR: char = ';';
+
+// The row number, counting from 1, and a cell for each header.
+pub(crate) type CsvRow = (usize, Vec<Option<String>>);
+
+#[derive(Clone, Debug, Default)]
+pub struct WordList {
+    name: String,
+    entries: Vec<WordEntry>,
+    removed_duplicates: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileFormat {
+    Text,
+    Csv,
+    Json,
+}
+
+#[derive(Debug)]
+pub enum WordListError {
+    Io { path: String, error: std::io::Error },
+    Parse { path: String, message: String },
+    UnknownFormat { path: String },
+}
+
+// A JSON word list is either a bare array or an object with a name. Either way each item can be a
+// plain word or an object with the fields of WordEntry.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonWordList {
+    Words(Vec<JsonWordEntry>),
+    Named { name: Option<String>, words: Vec<JsonWordEntry> },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonWordEntry {
+    Word(String),
+    Entry(WordEntry),
+}
+
+#[derive(Serialize)]
+struct JsonWordListOut<'a> {
+    name: &'a str,
+    words: &'a Vec<WordEntry>,
+}
+
+impl WordList {
+    /// Words are trimmed, blank entries are skipped, and any word that repeats an earlier one
+    /// (ignoring case) is dropped and remembered in removed_duplicates.
+    pub fn new<S: AsRef<str>>(name: &str, words: &[S]) -> Self {
+        Self::from_entries(name, words.iter().map(|word| WordEntry::new(word.as_ref())).collect())
+    }
+
+    pub fn from_entries(name: &str, entries: Vec<WordEntry>) -> Self {
+        let mut list = Self {
+            name: name.to_string(),
+            entries: vec![],
+            removed_duplicates: vec![],
+        };
+        let mut seen = BTreeSet::new();
+        for mut entry in entries.into_iter() {
+            entry.word = entry.word.trim().to_string();
+            if entry.word.is_empty() {
+                continue;
+            }
+            if seen.insert(entry.word.to_lowercase()) {
+                list.entries.push(entry);
+            } else {
+                list.removed_duplicates.push(entry.word);
+            }
+        }
+        list
+    }
+
+    /// Find a built-in list such as WORDS_1 or ALL_SECOND_GRADE.
+    pub fn named(name: &str) -> Option<Self> {
+        let name = name.trim().to_uppercase();
+        let mut list = Self::new(&name, word_list::get_named_list(&name)?);
+        if name == "ALL_SECOND_GRADE" {
+            list.entries.iter_mut().for_each(|entry| entry.grade = Some(2));
+        }
+        Some(list)
+    }
+
+    pub fn read_file(path: &str) -> Result<Self, WordListError> {
+        let format = FileFormat::from_path(path)?;
+        let text = std::fs::read_to_string(path).map_err(|error| WordListError::Io { path: path.to_string(), error })?;
+        let name = Path::new(path).file_stem().map_or("".to_string(), |stem| stem.to_string_lossy().to_string());
+        Self::parse(&name, &text, format).map_err(|message| WordListError::Parse { path: path.to_string(), message })
+    }
+
+    pub fn parse(name: &str, text: &str, format: FileFormat) -> Result<Self, String> {
+        match format {
+            FileFormat::Text => Ok(Self::new(name, &parse_text_lines(text))),
+            FileFormat::Csv => Self::parse_csv(name, text),
+            FileFormat::Json => Self::parse_json(name, text),
+        }
+    }
+
+    fn parse_csv(name: &str, text: &str) -> Result<Self, String> {
+        let mut entries = vec![];
+        for (row_number, cells) in parse_csv_rows(text, &CSV_HEADERS)? {
+            let get = |header_index: usize| cells[header_index].as_deref();
+            let mut entry = WordEntry::new(get(0).unwrap_or(""));
+            entry.grade = get(1).map(|grade| grade.parse::<u8>().map_err(|_| format!("Row {}: \"{}\" is not a grade.", row_number, grade))).transpose()?;
+            entry.definition = get(2).map(|definition| definition.to_string());
+            entry.example = get(3).map(|example| example.to_string());
+            entry.part_of_speech = get(4).map(|part_of_speech| part_of_speech.parse::<PartOfSpeech>().map_err(|e| format!("Row {}: {}", row_number, e))).transpose()?;
+            entry.tags = get(5).map_or(vec![], |tags| tags.split(CSV_TAG_SEPARATOR)
+                .map(|tag| tag.trim())
+                .filter(|tag| !tag.is_empty())
+                .map(|tag| tag.to_string())
+                .collect());
+            entries.push(entry);
+        }
+        Ok(Self::from_entries(name, entries))
+    }
+
+    fn parse_json(name: &str, text: &str) -> Result<Self, String> {
+        let (json_name, words) = match serde_json::from_str::<JsonWordList>(text).map_err(|e| e.to_string())? {
+            JsonWordList::Words(words) => (None, words),
+            JsonWordList::Named { name, words } => (name, words),
+        };
+        let entries = words.into_iter()
+            .map(|word| match word {
+                JsonWordEntry::Word(word) => WordEntry::new(&word),
+                JsonWordEntry::Entry(entry) => entry,
+            })
+            .collect();
+        Ok(Self::from_entries(&json_name.unwrap_or(name.to_string()), entries))
+    }
+
+    pub fn write_file(&self, path: &str) -> Result<(), WordListError> {
+        let format = FileFormat::from_path(path)?;
+        std::fs::write(path, self.to_format_string(format)).map_err(|error| WordListError::Io { path: path.to_string(), error })
+    }
+
+    /// Plain text holds only the words, so any metadata is lost. Use CSV or JSON to keep it.
+    pub fn to_format_string(&self, format: FileFormat) -> String {
+        match format {
+            FileFormat::Text => {
+                let mut s = if self.name.is_empty() { "".to_string() } else { format!("{} {}\n", COMMENT_CHAR, self.name) };
+                self.entries.iter().for_each(|entry| s.push_str(&format!("{}\n", entry.word)));
+                s
+            },
+            FileFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(vec![]);
+                writer.write_record(CSV_HEADERS).unwrap();
+                for entry in self.entries.iter() {
+                    writer.write_record(&[
+                        entry.word.clone(),
+                        entry.grade.map_or("".to_string(), |grade| grade.to_string()),
+                        entry.definition.clone().unwrap_or_default(),
+                        entry.example.clone().unwrap_or_default(),
+                        entry.part_of_speech.map_or("".to_string(), |part_of_speech| part_of_speech.to_string()),
+                        entry.tags.join(&CSV_TAG_SEPARATOR.to_string()),
+                    ]).unwrap();
+                }
+                String::from_utf8(writer.into_inner().unwrap()).unwrap()
+            },
+            FileFormat::Json => {
+                let json = JsonWordListOut { name: &self.name, words: &self.entries };
+                serde_json::to_string_pretty(&json).unwrap() + "\n"
+            },
+        }
+    }
+
+    /// Rust source for a const that can be pasted into word_list.
+    pub fn to_rust_const(&self, const_name: &str) -> String {
+        let mut s = format!("pub const {}: [&str; {}] = [\n", const_name, self.entries.len());
+        self.entries.iter().for_each(|entry| s.push_str(&format!("\t\"{}\",\n", entry.word)));
+        s.push_str("];\n");
+        s
+    }
+
+    /// A copy of the list with only the entries that pass the filter.
+    pub fn filter<F>(&self, f: F) -> Self
+        where F: Fn(&WordEntry) -> bool
+    {
+        Self {
+            name: self.name.clone(),
+            entries: self.entries.iter().filter(|entry| f(entry)).cloned().collect(),
+            removed_duplicates: vec![],
+        }
+    }
+
+    pub fn filter_by_tag(&self, tag: &str) -> Self {
+        self.filter(|entry| entry.has_tag(tag))
+    }
+
+    pub fn filter_by_grade(&self, grade: u8) -> Self {
+        self.filter(|entry| entry.grade == Some(grade))
+    }
+
+    /// All distinct tags in the order they first appear.
+    pub fn get_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = vec![];
+        for tag in self.entries.iter().flat_map(|entry| entry.tags.iter()) {
+            if !tags.iter().any(|found_tag| found_tag.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+        tags
+    }
+
+    /// One line per word with its definition where there is one, sorted by word.
+    pub fn get_clue_string(&self) -> String {
+        self.entries.iter()
+            .sorted_by(|a, b| a.word.to_lowercase().cmp(&b.word.to_lowercase()))
+            .enumerate()
+            .map(|(index, entry)| format!("{}. {}\n", index + 1, entry.get_clue().unwrap_or_else(|| entry.word.to_uppercase())))
+            .join("")
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_entries(&self) -> &Vec<WordEntry> {
+        &self.entries
+    }
+
+    pub fn get_entry(&self, word: &str) -> Option<&WordEntry> {
+        self.entries.iter().find(|entry| entry.word.eq_ignore_ascii_case(word.trim()))
+    }
+
+    pub fn get_words(&self) -> Vec<String> {
+        self.entries.iter().map(|entry| entry.word.clone()).collect()
+    }
+
+    pub fn get_removed_duplicates(&self) -> &Vec<String> {
+        &self.removed_duplicates
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.get_entry(word).is_some()
+    }
+}
+
+impl FileFormat {
+    pub fn from_path(path: &str) -> Result<Self, WordListError> {
+        let extension = Path::new(path).extension().map_or("".to_string(), |extension| extension.to_string_lossy().to_lowercase());
+        match extension.as_str() {
+            "txt" | "text" | "" => Ok(FileFormat::Text),
+            "csv" => Ok(FileFormat::Csv),
+            "json" => Ok(FileFormat::Json),
+            _ => Err(WordListError::UnknownFormat { path: path.to_string() }),
+        }
+    }
+}
+
+impl Display for WordListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            WordListError::Io { path, error } => write!(f, "Unable to access \"{}\": {}", path, error),
+            WordListError::Parse { path, message } => write!(f, "Unable to read \"{}\": {}", path, message),
+            WordListError::UnknownFormat { path } => write!(f, "\"{}\" should end in .txt, .csv or .json.", path),
+        }
+    }
+}
+
+impl std::error::Error for WordListError {}
+
+/// Split plain text into trimmed lines, dropping blank lines and anything after a '#'.
+pub(crate) fn parse_text_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split(COMMENT_CHAR).next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Read CSV text into one cell per header for each row, trimmed, with None for a cell that's
+/// missing or empty. Rows starting with a '#' are skipped. Without a header row starting with
+/// headers[0], the first column is that one and there's nothing else. With one, the other columns
+/// can be in any order and any of them can be left out. Each row comes with the line it starts on,
+/// counting comments and blank lines, for error messages.
+pub(crate) fn parse_csv_rows(text: &str, headers: &[&str]) -> Result<Vec<CsvRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .comment(Some(COMMENT_CHAR as u8))
+        .from_reader(text.as_bytes());
+    let mut column_indexes: Vec<Option<usize>> = vec![None; headers.len()];
+    column_indexes[0] = Some(0);
+    let mut rows = vec![];
+    for (row_index, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| e.to_string())?;
+        if row_index == 0 && record.get(0).is_some_and(|cell| cell.trim().eq_ignore_ascii_case(headers[0])) {
+            for (header_index, header) in headers.iter().enumerate() {
+                column_indexes[header_index] = record.iter().position(|cell| cell.trim().eq_ignore_ascii_case(header));
+            }
+            continue;
+        }
+        let cells = column_indexes.iter()
+            .map(|column_index| column_index
+                .and_then(|column_index| record.get(column_index))
+                .map(|cell| cell.trim())
+                .filter(|cell| !cell.is_empty())
+                .map(|cell| cell.to_string()))
+            .collect();
+        let line = record.position().map_or(row_index + 1, |position| get_line_number(text, position.byte() as usize));
+        rows.push((line, cells));
+    }
+    Ok(rows)
+}
+
+// The reader gives the position just after the previous record, and its own line count leaves out
+// comments, so count the lines up to there and then past any comments and blank lines.
+fn get_line_number(text: &str, byte: usize) -> usize {
+    let line_number = text[..byte].matches('\n').count() + 1;
+    let skipped_count = text[byte..].lines()
+        .take_while(|line| line.is_empty() || line.starts_with(COMMENT_CHAR))
+        .count();
+    line_number + skipped_count
+}
@@ -1,20 +1,28 @@
-use rand::thread_rng;
-use rand::seq::SliceRandom;
+pub use entry::*;
+pub use lint::*;
+pub use list::*;
+pub use sample::*;
 
-pub fn make_random_sublist(word_count: usize, words: &[&str]) {
-    debug_assert!(word_count <= words.len());
-    let mut words = words.iter().map(|word| word.to_string()).collect::<Vec<_>>();
-    words.shuffle(&mut thread_rng());
-    let mut sublist = Vec::with_capacity(word_count);
-    for i in 0..word_count {
-        sublist.push(words[i].clone());
-    }
-    sublist.sort();
-    println!("pub const WORDS: [&str; {}] = [", word_count);
-    for word in sublist.iter() {
-        println!("\t\"{}\",", word);
+pub mod entry;
+pub mod lint;
+pub mod list;
+pub mod sample;
+
+/// Pick word_count words at random with no other constraints. See SampleSettings for more control.
+pub fn make_random_sublist(word_count: usize, words: &[&str]) -> Result<Vec<String>, String> {
+    SampleSettings::default().sample(&WordList::new("", words), word_count)
+}
+
+/// Look up one of the lists below by the name of its const, ignoring case.
+pub fn get_named_list(name: &str) -> Option<&'static [&'static str]> {
+    match name.trim().to_uppercase().as_str() {
+        "WORDS_1" => Some(&WORDS_1),
+        "WORDS_2" => Some(&WORDS_2),
+        "WORDS_3" => Some(&WORDS_3),
+        "WORDS_4" => Some(&WORDS_4),
+        "ALL_SECOND_GRADE" => Some(&ALL_SECOND_GRADE),
+        _ => None,
     }
-    println!("];")
 }
 
 pub const WORDS_1: [&str; 10] = [
@@ -0,0 +1,125 @@
+use crate::random::rng;
+use super::entry::WordEntry;
+use super::list::WordList;
+
+use rand::seq::SliceRandom;
+use std::collections::BTreeSet;
+
+const TRY_COUNT_MAX: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct SampleSettings {
+    /// How many words to take from each range of lengths. Any words beyond the total of the bands
+    /// can be of any length.
+    pub length_bands: Vec<LengthBand>,
+    /// Each word after the first must share at least this many distinct letters with some word
+    /// already chosen. Lists where a word shares nothing with the others make loose grids.
+    pub shared_letters_min: usize,
+    /// Words to leave out, such as those used in last week's puzzles. Case is ignored.
+    pub exclude: Vec<String>,
+    /// If not empty, only words with at least one of these tags are used.
+    pub tags: Vec<String>,
+    pub grade: Option<u8>,
+    pub try_count_max: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct LengthBand {
+    pub length_min: usize,
+    pub length_max: usize,
+    pub count: usize,
+}
+
+impl SampleSettings {
+    /// Pick word_count words that satisfy the settings. The result is sorted.
+    pub fn sample(&self, list: &WordList, word_count: usize) -> Result<Vec<String>, String> {
+        let candidates = list.get_entries().iter()
+            .filter(|entry| self.is_candidate(entry))
+            .map(|entry| entry.word.clone())
+            .collect::<Vec<_>>();
+        if word_count > candidates.len() {
+            return Err(format!("Asked for {} words but only {} words match.", word_count, candidates.len()));
+        }
+        let band_total: usize = self.length_bands.iter().map(|band| band.count).sum();
+        if band_total > word_count {
+            return Err(format!("The length bands add up to {} words but only {} were asked for.", band_total, word_count));
+        }
+        for _ in 0..self.try_count_max.max(1) {
+            if let Some(mut sublist) = self.try_sample(&candidates, word_count) {
+                sublist.sort();
+                return Ok(sublist);
+            }
+        }
+        Err(format!("Unable to find {} words that meet the constraints after {} tries.", word_count, self.try_count_max.max(1)))
+    }
+
+    fn is_candidate(&self, entry: &WordEntry) -> bool {
+        let is_excluded = self.exclude.iter().any(|word| word.trim().eq_ignore_ascii_case(&entry.word));
+        let has_tag = self.tags.is_empty() || self.tags.iter().any(|tag| entry.has_tag(tag));
+        let has_grade = self.grade.is_none_or(|grade| entry.grade == Some(grade));
+        !is_excluded && has_tag && has_grade
+    }
+
+    fn try_sample(&self, candidates: &[String], word_count: usize) -> Option<Vec<String>> {
+        let mut candidates = candidates.to_vec();
+        candidates.shuffle(&mut rng());
+        let mut sublist: Vec<String> = vec![];
+        // Fill the bands first, then take whatever fits for the rest.
+        let catch_all = LengthBand { length_min: 0, length_max: usize::MAX, count: word_count - self.length_bands.iter().map(|band| band.count).sum::<usize>() };
+        for band in self.length_bands.iter().chain(std::iter::once(&catch_all)) {
+            let mut band_count = 0;
+            let mut index = 0;
+            while band_count < band.count && index < candidates.len() {
+                let word = &candidates[index];
+                let length = word.chars().count();
+                if length >= band.length_min && length <= band.length_max && self.shares_enough_letters(word, &sublist) {
+                    sublist.push(candidates.remove(index));
+                    band_count += 1;
+                    // A word passed over for sharing too few letters may fit now, so start over.
+                    index = 0;
+                } else {
+                    index += 1;
+                }
+            }
+            if band_count < band.count {
+                return None;
+            }
+        }
+        Some(sublist)
+    }
+
+    fn shares_enough_letters(&self, word: &str, sublist: &[String]) -> bool {
+        if self.shared_letters_min == 0 || sublist.is_empty() {
+            return true;
+        }
+        let letters = get_letter_set(word);
+        sublist.iter().any(|other| letters.intersection(&get_letter_set(other)).count() >= self.shared_letters_min)
+    }
+}
+
+impl Default for SampleSettings {
+    fn default() -> Self {
+        Self {
+            length_bands: vec![],
+            shared_letters_min: 0,
+            exclude: vec![],
+            tags: vec![],
+            grade: None,
+            try_count_max: TRY_COUNT_MAX,
+        }
+    }
+}
+
+impl LengthBand {
+    pub fn new(length_min: usize, length_max: usize, count: usize) -> Self {
+        Self {
+            length_min,
+            length_max,
+            count,
+        }
+    }
+}
+
+fn get_letter_set(word: &str) -> BTreeSet<char> {
+    word.to_lowercase().chars().filter(|c| c.is_alphabetic()).collect()
+}
@@ -2,4 +2,5 @@ pub use puzzle::*;
 
 pub use crate::word_list::*;
 
+pub mod play;
 pub mod puzzle;
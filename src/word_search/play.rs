@@ -0,0 +1,222 @@
+use crate::*;
+use super::puzzle::{Puzzle, Position, Direction, Settings};
+
+use std::collections::BTreeSet;
+use std::io::{stdout, Stdout, Write};
+use std::time::{Duration, Instant};
+use crossterm::{cursor, execute, queue, terminal};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+
+const GRID_LEFT: u16 = 2;
+const GRID_TOP: u16 = 3;
+const WORD_BANK_GAP: u16 = 6;
+const POLL_MILLIS: u64 = 250;
+
+pub struct Play {
+    puzzle: Puzzle,
+    cursor: Position,
+    selection_start: Option<Position>,
+    found_words: BTreeSet<String>,
+    start_time: Instant,
+    finish_time: Option<Instant>,
+    message: String,
+}
+
+impl Play {
+    pub fn new(mut puzzle: Puzzle) -> Result<Self, String> {
+        puzzle.random_fill_optional()?;
+        let bounds = puzzle.get_bounds();
+        let cursor = Position::new(bounds.get_x_min(), bounds.get_y_min());
+        Ok(Self {
+            puzzle,
+            cursor,
+            selection_start: None,
+            found_words: Default::default(),
+            start_time: Instant::now(),
+            finish_time: None,
+            message: "Arrow keys move, space or enter selects the first and last letters, q quits.".to_string(),
+        })
+    }
+
+    pub fn get_puzzle(&self) -> &Puzzle {
+        &self.puzzle
+    }
+
+    pub fn get_cursor(&self) -> &Position {
+        &self.cursor
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Set when the last word is found, which stops the timer.
+    pub fn get_finish_time(&self) -> Option<Instant> {
+        self.finish_time
+    }
+
+    pub fn run(&mut self) -> crossterm::Result<()> {
+        let mut out = stdout();
+        execute!(out, terminal::EnterAlternateScreen)?;
+        // From here on the terminal is put back however run() exits, including by an error or a
+        // panic.
+        let _restore = RestoreTerminal;
+        execute!(out, cursor::Hide)?;
+        terminal::enable_raw_mode()?;
+        self.event_loop(&mut out)
+    }
+
+    fn event_loop(&mut self, out: &mut Stdout) -> crossterm::Result<()> {
+        loop {
+            self.draw(out)?;
+            // Poll rather than block so that the timer keeps ticking while the player thinks.
+            if event::poll(Duration::from_millis(POLL_MILLIS))? {
+                if let Event::Key(key) = event::read()? {
+                    if !self.handle_key(key) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns false when the player wants to quit.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('q') => return false,
+            // Raw mode keeps Ctrl+C from stopping the program, so it has to quit here instead.
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(&Direction::N),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(&Direction::S),
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(&Direction::W),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(&Direction::E),
+            KeyCode::Char(' ') | KeyCode::Enter => self.select(),
+            KeyCode::Esc => {
+                self.selection_start = None;
+                self.message = "Selection cleared.".to_string();
+            },
+            _ => {},
+        }
+        true
+    }
+
+    fn move_cursor(&mut self, direction: &Direction) {
+        let bounds = self.puzzle.get_bounds();
+        let (x, y) = (self.cursor.get_x(), self.cursor.get_y());
+        let (x, y) = match direction {
+            Direction::N if y > bounds.get_y_min() => (x, y - 1),
+            Direction::S if y < bounds.get_y_max() => (x, y + 1),
+            Direction::W if x > bounds.get_x_min() => (x - 1, y),
+            Direction::E if x < bounds.get_x_max() => (x + 1, y),
+            _ => (x, y),
+        };
+        self.cursor = Position::new(x, y);
+    }
+
+    fn select(&mut self) {
+        if self.finish_time.is_some() {
+            return;
+        }
+        let start = match self.selection_start.take() {
+            Some(start) => start,
+            None => {
+                self.selection_start = Some(self.cursor.clone());
+                self.message = format!("Start at {}. Now select the last letter.", self.cursor);
+                return;
+            }
+        };
+        let found_word = self.puzzle.find_word_at(&start, &self.cursor).cloned();
+        self.message = match found_word {
+            Some(word) if self.found_words.contains(&word) => format!("\"{}\" was already found.", word),
+            Some(word) => {
+                let message = format!("Found \"{}\"!", word);
+                self.found_words.insert(word);
+                if self.found_words.len() == self.puzzle.get_words().len() {
+                    self.finish_time = Some(Instant::now());
+                    format!("All {} words found in {}. Press q to quit.", self.found_words.len(), format_elapsed(self.get_elapsed()))
+                } else {
+                    message
+                }
+            },
+            None => "No word there.".to_string(),
+        };
+    }
+
+    fn get_elapsed(&self) -> Duration {
+        self.finish_time.unwrap_or_else(Instant::now) - self.start_time
+    }
+
+    fn get_found_positions(&self) -> Vec<Position> {
+        self.found_words.iter()
+            .flat_map(|word| self.puzzle.get_word_positions(word))
+            .collect()
+    }
+
+    fn draw(&self, out: &mut Stdout) -> crossterm::Result<()> {
+        queue!(out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(GRID_LEFT, 0))?;
+        queue!(out, Print(format!("Word Search    time {}    found {} of {}",
+            format_elapsed(self.get_elapsed()), self.found_words.len(), self.puzzle.get_words().len())))?;
+        queue!(out, cursor::MoveTo(GRID_LEFT, 1), Print(&self.message))?;
+
+        let bounds = self.puzzle.get_bounds();
+        let found_positions = self.get_found_positions();
+        for (row, y) in (bounds.get_y_min()..=bounds.get_y_max()).enumerate() {
+            queue!(out, cursor::MoveTo(GRID_LEFT, GRID_TOP + row as u16))?;
+            for x in bounds.get_x_min()..=bounds.get_x_max() {
+                let position = Position::new(x, y);
+                let is_cursor = position == self.cursor;
+                let is_selection_start = self.selection_start.as_ref() == Some(&position);
+                let is_found = found_positions.contains(&position);
+                let foreground = if is_found { Color::Green } else { Color::White };
+                let background = match (is_cursor, is_selection_start) {
+                    (true, _) => Color::DarkBlue,
+                    (false, true) => Color::DarkYellow,
+                    (false, false) => Color::Reset,
+                };
+                let char = self.puzzle.get_char_xy(x, y).to_uppercase().to_string();
+                queue!(out, SetForegroundColor(foreground), SetBackgroundColor(background), Print(char), ResetColor, Print(" "))?;
+            }
+        }
+
+        // The word bank goes to the right of the grid with the remaining words first.
+        let bank_left = GRID_LEFT + (bounds.get_x_size() as u16 * 2) + WORD_BANK_GAP;
+        queue!(out, cursor::MoveTo(bank_left, GRID_TOP), Print("Words"))?;
+        let (found, remaining): (Vec<&String>, Vec<&String>) = self.puzzle.get_words().iter()
+            .sorted()
+            .partition(|word| self.found_words.contains(*word));
+        for (row, word) in remaining.iter().chain(found.iter()).enumerate() {
+            let color = if self.found_words.contains(*word) { Color::DarkGrey } else { Color::White };
+            queue!(out, cursor::MoveTo(bank_left, GRID_TOP + 2 + row as u16),
+                SetForegroundColor(color), Print(word.to_uppercase()), ResetColor)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        // Errors are ignored since there's nothing more to be done about them here, and raw mode
+        // may never have been turned on.
+        let _ = execute!(stdout(), ResetColor, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+pub fn main() {
+    let words = word_list::WORDS_1;
+    let settings = Settings {
+        directions: vec![Direction::E, Direction::SE, Direction::S],
+        ..Default::default()
+    };
+    let puzzle = Puzzle::find_best_puzzle(&slice_str_to_strings(&words), &settings).unwrap();
+    Play::new(puzzle).unwrap().run().unwrap();
+}
@@ -1,30 +1,51 @@
 use crate::*;
 
-use rand::{thread_rng, Rng};
+use crate::grid::{Grid, DIRECTIONS};
+use crate::random::rng;
+use rand::Rng;
 use rand::seq::SliceRandom;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::time::Instant;
 use itertools::Itertools;
 
 const PUZZLE_SIZE_MAX: usize = 30;
 const FIELD_SIZE_MULT: usize = 2;
+// Shown in empty cells. Whether a cell is taken goes by its word count instead, since a word
+// could contain this character too.
 const NO_CHAR: char = '-';
-const DIRECTIONS: [Direction; 8] = [Direction::N, Direction::NE, Direction::E, Direction::SE, Direction::S, Direction::SW, Direction::W, Direction::NW];
 const ASCII_A_LOWERCASE: u8 = 97;
-const PRIORITIZE_INTERSECTIONS: bool = false;
+const RANDOM_FILL_TRY_COUNT_MAX: usize = 100;
 
-type Grid = Vec<Vec<Cell>>;
-type Offset = [isize; 2];
+pub use crate::grid::{Direction, Position};
 
 #[derive(Clone)]
 pub struct Puzzle {
     words: Vec<String>,
-    expansion: f32,
-    directions: Vec<Direction>,
+    settings: Settings,
     is_random_filled: bool,
-    grid: Grid,
+    grid: Grid<Cell>,
     bounds: Bounds,
     placements: BTreeMap<String, Placement>,
+    char_map: BTreeMap<char, Vec<Position>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Settings {
+    pub expansion: f32,
+    pub directions: Vec<Direction>,
+    pub try_count_max: usize,
+    /// Stop trying new puzzles after this many seconds even if try_count_max hasn't been reached.
+    pub create_seconds_max: Option<u64>,
+    pub candidate_search: CandidateSearch,
+    pub ranking: Ranking,
+    /// Don't place a word entirely within the cells of another word, and keep only puzzles where
+    /// each word can be found in just one place. A list where one word is inside another forward
+    /// or backward, like "sum" in "summary", is an error since that can't be helped.
+    pub forbid_contained_words: bool,
+    /// Don't let two words run along the same line through a shared cell, where they would look
+    /// like one long word.
+    pub prevent_same_axis_overlap: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -32,6 +53,13 @@ pub struct Cell {
     char: char,
     word_count: usize,
     is_word_start: bool,
+    has_n_s: bool,
+    has_ne_sw: bool,
+    has_e_w: bool,
+    has_se_nw: bool,
+    // For each axis, the number of filled neighboring cells that aren't along that axis. Kept up
+    // to date as letters are placed so that scoring a placement doesn't have to look around.
+    adjacent_counts: [u8; 4],
 }
 
 #[derive(Clone, Debug)]
@@ -45,28 +73,30 @@ pub struct Placement {
     bounds: Bounds,
 }
 
-#[derive(Clone, Debug)]
-pub struct Position {
-    x: usize,
-    y: usize,
-}
-
 #[derive(Clone, Debug)]
 pub struct Bounds {
     top_left: Position,
     bottom_right: Position,
 }
 
-#[derive(Clone, Debug)]
-pub enum Direction {
-    N,
-    NE,
-    E,
-    SE,
-    S,
-    SW,
-    W,
-    NW,
+/// How to come up with the placements to choose from for each word.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CandidateSearch {
+    /// Every position within the current bounds in every direction.
+    FullScan,
+    /// Only placements that cross an existing word at a matching letter, falling back to a full
+    /// scan for a word that can't cross anything.
+    IntersectionFirst,
+}
+
+/// How to choose among the candidate placements for a word, and among the finished puzzles.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Ranking {
+    /// Favor small bounds and placements tucked in beside existing words, with the expansion
+    /// setting deciding how far down the ranked list to go.
+    Compact,
+    /// Favor the most intersections, then the smallest bounds.
+    Intersections,
 }
 
 pub enum ExcelStyle {
@@ -76,226 +106,355 @@ pub enum ExcelStyle {
 }
 
 impl Puzzle {
-    pub fn new(words: &Vec<String>, expansion: f32) -> Self {
-        debug_assert!(!words.is_empty());
-        let words = words.iter().map(|word| word.trim().to_lowercase()).collect::<Vec<_>>();
-        let size = words.iter().map(|word| word.len()).max().unwrap();
-        debug_assert!(size <= PUZZLE_SIZE_MAX);
+    pub fn new(words: &[String], settings: &Settings) -> Result<Self, String> {
+        // A repeated word would be placed twice but could only be looked up in one place.
+        let words = words.iter()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .unique()
+            .collect::<Vec<_>>();
+        if words.is_empty() {
+            return Err("A word search needs at least one word.".to_string());
+        }
+        if let Some(word) = words.iter().find(|word| word.chars().count() > PUZZLE_SIZE_MAX) {
+            return Err(format!("\"{}\" is longer than the limit of {} letters.", word, PUZZLE_SIZE_MAX));
+        }
+        if settings.directions.is_empty() {
+            return Err("A word search needs at least one direction.".to_string());
+        }
+        if !(0.0..=1.0).contains(&settings.expansion) {
+            return Err(format!("The expansion must be from 0.0 to 1.0, not {}.", settings.expansion));
+        }
+        if settings.forbid_contained_words {
+            if let Some((word, other)) = Self::find_contained_word(&words) {
+                return Err(format!("\"{}\" is inside \"{}\" forward or backward, so it would be in more than one place.", word, other));
+            }
+        }
+        let size = words.iter().map(|word| word.chars().count()).max().unwrap();
         let x_max = PUZZLE_SIZE_MAX + (size / 2);
         let x_min = x_max - (size - 1);
         let y_max = x_max;
         let y_min = x_min;
-        Self {
+        Ok(Self {
             words,
-            expansion,
-            directions: DIRECTIONS.iter().map(|x| x.clone()).collect(),
+            settings: settings.clone(),
             is_random_filled: false,
             grid: Self::create_grid(),
             bounds: Bounds::new(Position::new(x_min, y_min), Position::new(x_max, y_max)),
             placements: Default::default(),
-        }
+            char_map: Default::default(),
+        })
     }
 
-    fn create_grid() -> Grid {
+    fn create_grid() -> Grid<Cell> {
         let grid_size = Self::get_field_size();
-        let mut grid = Vec::with_capacity(grid_size);
-        for _y in 0..grid_size {
-            let mut row = Vec::with_capacity(grid_size);
-            for _x in 0..grid_size {
-                row.push(Cell::new());
-            }
-            grid.push(row);
-        }
-        grid
+        Grid::new(grid_size, grid_size, Cell::new())
+    }
+
+    // The first word found inside another word in the list, forward or backward, along with the
+    // longer word.
+    fn find_contained_word(words: &[String]) -> Option<(&String, &String)> {
+        words.iter()
+            .flat_map(|word| words.iter().map(move |other| (word, other)))
+            .find(|(word, other)| word != other && other.len() >= word.len()
+                && (other.contains(word.as_str()) || other.contains(&word.chars().rev().collect::<String>())))
     }
 
-    pub fn find_best_puzzle(words: &Vec<String>, expansion: f32, directions: Option<Vec<Direction>>, try_count_max: usize) -> Self {
+    pub fn find_best_puzzle(words: &[String], settings: &Settings) -> Result<Self, String> {
         let mut try_count = 1;
+        let start_time = Instant::now();
         let mut puzzles = vec![];
         loop {
-            let mut puzzle = Self::new(words, expansion);
-            if let Some(ref directions) = directions {
-                puzzle.directions = directions.clone();
-            }
-            puzzle.create();
+            let mut puzzle = Self::new(words, settings)?;
+            puzzle.create()?;
             //puzzle.print_puzzle();
-            puzzle.print_all();
+            //puzzle.print_all();
             puzzles.push(puzzle);
             try_count += 1;
-            if try_count > try_count_max {
+            if try_count > settings.try_count_max {
                 break;
             }
+            if let Some(create_seconds_max) = settings.create_seconds_max {
+                if start_time.elapsed().as_secs() >= create_seconds_max {
+                    break;
+                }
+            }
         }
-        if PRIORITIZE_INTERSECTIONS {
-            puzzles.sort_by(|a, b| a.get_intersection_score().cmp(&b.get_intersection_score()).reverse());
-        } else {
-            puzzles.sort_by(|a, b| a.bounds.get_size().cmp(&b.bounds.get_size()));
+        match settings.ranking {
+            Ranking::Intersections => puzzles.sort_by(|a, b| a.get_intersection_score().cmp(&b.get_intersection_score()).reverse()),
+            Ranking::Compact => puzzles.sort_by_key(|puzzle| puzzle.bounds.get_size()),
+        }
+        if settings.forbid_contained_words {
+            // Puzzles where every word is in only one place go first. The sort is stable so the
+            // order above still holds within each group.
+            puzzles.sort_by_cached_key(|puzzle| puzzle.find_ambiguous_words().len());
+            let ambiguous_words = puzzles[0].find_ambiguous_words();
+            if !ambiguous_words.is_empty() {
+                return Err(format!("Unable to place the words so that each is in only one place after {} tries. Found more than once: {}.", puzzles.len(), ambiguous_words.join(", ")));
+            }
         }
-        puzzles.remove(0)
+        Ok(puzzles.remove(0))
     }
 
-    fn create(&mut self) {
-        self.words.shuffle(&mut thread_rng());
-        self.words.sort_by(|a, b| a.len().cmp(&b.len()).reverse());
+    fn create(&mut self) -> Result<(), String> {
+        self.words.shuffle(&mut rng());
+        self.words.sort_by(|a, b| a.chars().count().cmp(&b.chars().count()).reverse());
         let mut words = self.words.clone();
         while !words.is_empty() {
-            self.place_word(words.remove(0));
+            self.place_word(words.remove(0))?;
         }
+        Ok(())
     }
 
-    fn place_word(&mut self, word: String) {
-        // Try all possible placements.
-        let mut placements = vec![];
-        for x in self.bounds.get_x_min()..=self.bounds.get_x_max() {
-            for y in self.bounds.get_y_min()..=self.bounds.get_y_max() {
-                let position = Position::new(x, y);
-                for direction in self.directions.iter() {
-                    if let Some(placement) = self.try_placement(&word, 0, &position, direction) {
-                        placements.push(placement);
-                    }
-                }
-            }
+    fn place_word(&mut self, word: String) -> Result<(), String> {
+        let mut placements = match self.settings.candidate_search {
+            CandidateSearch::IntersectionFirst => self.get_intersection_placements(&word),
+            CandidateSearch::FullScan => vec![],
+        };
+        if placements.is_empty() {
+            placements = self.get_all_placements(&word, 0);
+        }
+        if placements.is_empty() {
+            // Every cell in the bounds is taken or blocked, so let the word start outside them.
+            placements = self.get_all_placements(&word, word.chars().count());
+        }
+        if placements.is_empty() {
+            return Err(format!("There's no room left for \"{}\".", word));
         }
         let mut chosen_placement_index = 0;
         if placements.len() > 1 {
 
-            placements.shuffle(&mut thread_rng());
+            placements.shuffle(&mut rng());
 
-            if PRIORITIZE_INTERSECTIONS {
+            if self.settings.ranking == Ranking::Intersections {
 
-                placements.sort_by(|a, b| a.get_intersection_score().cmp(&b.get_intersection_score()).reverse());
+                // Keep the placements with the most intersections, and of those the smallest
+                // required size.
+                placements.sort_by(|a, b| a.get_intersection_score().cmp(&b.get_intersection_score()).reverse()
+                    .then(a.bounds.get_size().cmp(&b.bounds.get_size())));
 
             } else {
 
                 // Set the size rankings. The smallest sizes go first and get the smallest rank
                 // numbers.
-                placements.sort_by(|a, b| a.bounds.get_size().cmp(&b.bounds.get_size()));
+                placements.sort_by_key(|placement| placement.bounds.get_size());
                 placements.iter_mut().enumerate().for_each(|(i, placement)| placement.size_rank = i);
 
                 // Set the adjacent count rankings. The _highest_ adjacent counts go first and get
                 // the smallest rank numbers.
-                placements.shuffle(&mut thread_rng());
+                placements.shuffle(&mut rng());
                 placements.sort_by(|a, b| a.adjacent_count.cmp(&b.adjacent_count).reverse());
                 placements.iter_mut().enumerate().for_each(|(i, placement)| placement.adjacent_rank = i);
 
                 // Sort by the combined ranks.
-                placements.shuffle(&mut thread_rng());
-                placements.sort_by(|a, b| (a.size_rank + a.adjacent_rank).cmp(&(b.size_rank + &b.adjacent_rank)));
+                placements.shuffle(&mut rng());
+                placements.sort_by_key(|placement| placement.size_rank + placement.adjacent_rank);
 
                 // Choose an entry from the top of the list (self.expansion is 0.0, as compact as
                 // passible), the end of the list (self.expansion is 1.0, as loose as possible), or
                 // somewhere in between.
-                chosen_placement_index = ((placements.len() as f32 - 1.0) * self.expansion).floor() as usize;
+                chosen_placement_index = ((placements.len() as f32 - 1.0) * self.settings.expansion).floor() as usize;
             }
         }
         self.apply_word_placement(word, placements.remove(chosen_placement_index));
+        Ok(())
+    }
+
+    // Try every start position within the bounds plus the given margin on each side.
+    fn get_all_placements(&self, word: &str, margin: usize) -> Vec<Placement> {
+        let mut placements = vec![];
+        let position_max = Self::get_field_size() - 1;
+        for x in self.bounds.get_x_min().saturating_sub(margin)..=(self.bounds.get_x_max() + margin).min(position_max) {
+            for y in self.bounds.get_y_min().saturating_sub(margin)..=(self.bounds.get_y_max() + margin).min(position_max) {
+                let position = Position::new(x, y);
+                for direction in self.settings.directions.iter() {
+                    if let Some(placement) = self.try_placement(word, 0, &position, direction) {
+                        placements.push(placement);
+                    }
+                }
+            }
+        }
+        placements
+    }
+
+    // Rather than scanning every cell in the bounds, line the word up with each existing cell that
+    // holds one of its letters. That's far fewer candidates once the grid fills up, and every one
+    // of them crosses at least one other word.
+    fn get_intersection_placements(&self, word: &str) -> Vec<Placement> {
+        let mut placements = vec![];
+        for (char_index, char) in word.chars().enumerate() {
+            if let Some(positions) = self.char_map.get(&char) {
+                // This is a list of positions where this character already appears on the puzzle.
+                for position in positions.iter() {
+                    for direction in self.settings.directions.iter() {
+                        if let Some(placement) = self.try_placement(word, char_index, position, direction) {
+                            placements.push(placement);
+                        }
+                    }
+                }
+            }
+        }
+        // A placement that crosses two or more words is found once for each crossing. Keep one
+        // copy so it isn't more likely to be chosen than a placement with a single crossing.
+        placements.sort_by_key(|placement| (placement.position.get_y(), placement.position.get_x(), placement.direction));
+        placements.dedup_by(|a, b| a.position == b.position && a.direction == b.direction);
+        placements
     }
     
-    fn try_placement(&self, word: &String, char_index: usize, position: &Position, direction: &Direction) -> Option<Placement> {
+    fn try_placement(&self, word: &str, char_index: usize, position: &Position, direction: &Direction) -> Option<Placement> {
+        // We're starting at the proposed intersection of a new word with an existing word at some
+        // character in the new word, so back up to the first character of the new word.
+        let position_new_word = position.offset(direction.opposite(), char_index)?;
+        let length = word.chars().count();
+        let line = self.grid.get_line(&position_new_word, *direction, length)?;
+        // The word runs in a straight line, so its two ends are enough to work out the new bounds.
+        let mut bounds = self.bounds.clone();
+        bounds.apply_position(&position_new_word);
+        bounds.apply_position(&Self::get_end_position(&position_new_word, direction, length));
+        let axis = direction.get_axis();
         let mut intersection_count = 0;
         let mut adjacent_count = 0;
-        let mut bounds = self.bounds.clone();
-        let position_new_word = position.back_to_word_start_optional(char_index, direction, Self::get_field_size());
-        if position_new_word.is_none() {
-            return None;
-        }
-        let position_new_word = position_new_word.unwrap();
-        let mut pos = position_new_word.clone();
-        if !self.is_placement_on_grid(word.len(), &pos, direction) {
-            return None;
-        }
-        let offset = direction.get_offset();
-        for char in word.chars() {
-            let cell = self.get_cell(&pos);
-            if cell.char != NO_CHAR && cell.char != char {
+        let mut has_same_axis_cell = false;
+        for (char, cell) in word.chars().zip(line) {
+            if cell.word_count > 0 && cell.char != char {
                 // There's already a character in this cell and it doesn't match the character in
                 // the new word.
                 return None;
             }
-            if cell.char == char {
+            if cell.has_direction_conflict(direction) {
+                if self.settings.prevent_same_axis_overlap {
+                    // The character matches but the existing word runs along the same line.
+                    return None;
+                }
+                has_same_axis_cell = true;
+            }
+            if cell.word_count > 0 {
                 intersection_count += 1;
             } else {
                 // If we use this placement we'll be adding a character at this position. Count the
                 // number of filled cells touching this one.
-                adjacent_count += self.get_adjacent_count(&pos, direction);
+                adjacent_count += cell.adjacent_counts[axis] as usize;
             }
-            bounds.apply_position(&pos);
-            pos.apply_offset(&offset);
         }
-        let placement = Placement::new(position_new_word, direction.clone(), intersection_count, adjacent_count, bounds);
+        // One word can only contain another if they share cells along the same line, so the
+        // slower check is needed only when the loop above found such a cell.
+        if self.settings.forbid_contained_words && has_same_axis_cell && self.is_contained_placement(length, &position_new_word, direction) {
+            return None;
+        }
+        let placement = Placement::new(position_new_word, *direction, intersection_count, adjacent_count, bounds);
         Some(placement)
     }
 
-    fn is_placement_on_grid(&self, word_length: usize, position: &Position, direction: &Direction) -> bool {
-        let word_length = word_length as isize;
-        let field_size = Self::get_field_size();
-        let offset = direction.get_offset();
-        let x_end = position.x as isize + (word_length * offset[0]);
-        let y_end = position.y as isize + (word_length * offset[1]);
-        position.x < field_size && x_end >= 0 && x_end < field_size as isize
-            && position.y < field_size && y_end >= 0 && y_end < field_size as isize
-    }
-
-    fn get_adjacent_count(&self, position: &Position, direction: &Direction) -> usize {
-        let dir_this = direction.get_variant_name().to_string();
-        let dir_opposite = direction.opposite().get_variant_name().to_string();
-        let mut count = 0;
-        for dir in DIRECTIONS.iter()
-                .filter(|dir| dir.get_variant_name().ne(dir_this.as_str()) && dir.get_variant_name().ne(dir_opposite.as_str())) {
-            let offset = dir.get_offset();
-            let mut pos = position.clone();
-            pos.apply_offset(&offset);
-            if self.get_cell(&pos).char != NO_CHAR {
-                count += 1;
+    // Would the new word lie entirely within an existing word's cells, or cover all of them?
+    fn is_contained_placement(&self, word_length: usize, position: &Position, direction: &Direction) -> bool {
+        let new_positions = Placement::new(position.clone(), *direction, 0, 0, self.bounds.clone()).get_positions(word_length);
+        self.placements.iter().any(|(word, placement)| {
+            let positions = placement.get_positions(word.chars().count());
+            new_positions.iter().all(|pos| positions.contains(pos)) || positions.iter().all(|pos| new_positions.contains(pos))
+        })
+    }
+
+    // Only for placements already known to be on the grid.
+    fn get_end_position(position: &Position, direction: &Direction, word_length: usize) -> Position {
+        position.offset(*direction, word_length.max(1) - 1).unwrap()
+    }
+
+    // A new letter at this position touches each of its neighbors. Each neighbor counts it for the
+    // axes other than the one joining the two cells.
+    fn update_adjacent_counts(&mut self, position: &Position) {
+        for (direction, neighbor) in self.grid.get_neighbors(position) {
+            let axis = direction.get_axis();
+            for (other_axis, count) in self.grid[&neighbor].adjacent_counts.iter_mut().enumerate() {
+                if other_axis != axis {
+                    *count += 1;
+                }
             }
         }
-        count
     }
 
     fn apply_word_placement(&mut self, word: String, placement: Placement) {
-        let offset = placement.direction.get_offset();
-        let mut pos = placement.position.clone();
-        for (char_index, char) in word.chars().enumerate() {
+        for (char_index, (char, pos)) in word.chars().zip(placement.get_positions(word.chars().count())).enumerate() {
             self.bounds.apply_position(&pos);
-            let cell = self.get_cell_mut(&pos);
+            let cell = &mut self.grid[&pos];
             let found_char = cell.char;
-            if found_char != NO_CHAR && found_char != char {
+            let is_new_char = cell.word_count == 0;
+            if !is_new_char && found_char != char {
                 self.print_all();
                 panic!("Trying to place word \"{}\" with {}. Conflicting character at {}: '{}'.",
                     &word, &placement, &pos, found_char);
             }
             cell.char = char;
             cell.word_count += 1;
+            cell.set_direction_flag(&placement.direction);
             if char_index == 0 {
                 cell.is_word_start = true;
             }
-            pos.apply_offset(&offset);
+            if is_new_char {
+                self.char_map.entry(char).or_insert(vec![]).push(pos.clone());
+                self.update_adjacent_counts(&pos);
+            }
         }
         self.placements.insert(word, placement);
     }
 
-    fn get_cell(&self, position: &Position) -> &Cell {
-        &self.grid[position.y][position.x]
+    pub fn get_char(&self, position: &Position) -> char {
+        self.grid[position].char
+    }
+
+    pub fn get_char_xy(&self, x: usize, y: usize) -> char {
+        self.grid[&Position::new(x, y)].char
     }
 
-    fn get_cell_xy(&self, x: usize, y: usize) -> &Cell {
-        &self.grid[y][x]
+    pub fn get_bounds(&self) -> &Bounds {
+        &self.bounds
     }
 
-    fn get_cell_mut(&mut self, position: &Position) -> &mut Cell {
-        &mut self.grid[position.y][position.x]
+    pub fn get_grid(&self) -> &Grid<Cell> {
+        &self.grid
     }
 
-    fn get_cell_mut_xy(&mut self, x: usize, y: usize) -> &mut Cell {
-        &mut self.grid[y][x]
+    pub fn get_placements(&self) -> &BTreeMap<String, Placement> {
+        &self.placements
     }
 
-    pub fn get_char(&self, position: &Position) -> char {
-        self.grid[position.y][position.x].char
+    pub fn get_words(&self) -> &Vec<String> {
+        &self.words
     }
 
-    pub fn get_char_xy(&self, x: usize, y: usize) -> char {
-        self.grid[y][x].char
+    /// Returns the word whose placement runs from start to end, in either order, so that a player
+    /// can select a word from its first or its last letter. A copy of a word spelled somewhere
+    /// else, such as by the random fill, counts too since the player can't tell it apart.
+    pub fn find_word_at(&self, start: &Position, end: &Position) -> Option<&String> {
+        self.placements.iter()
+            .find(|(word, placement)| {
+                let word_end = placement.get_end_position(word.chars().count());
+                (placement.position == *start && word_end == *end) || (placement.position == *end && word_end == *start)
+            })
+            .map(|(word, _placement)| word)
+            .or_else(|| self.find_word_spelled_at(start, end))
+    }
+
+    fn find_word_spelled_at(&self, start: &Position, end: &Position) -> Option<&String> {
+        let x_steps = (start.get_x() as isize - end.get_x() as isize).abs();
+        let y_steps = (start.get_y() as isize - end.get_y() as isize).abs();
+        let length = x_steps.max(y_steps) as usize + 1;
+        let positions = DIRECTIONS.iter()
+            .filter_map(|direction| self.grid.get_line_positions(start, *direction, length))
+            .find(|positions| positions.last() == Some(end))?;
+        let letters = positions.iter().map(|position| self.get_char(position)).collect::<String>();
+        let reversed = letters.chars().rev().collect::<String>();
+        self.words.iter().find(|word| **word == letters || **word == reversed)
+    }
+
+    pub fn get_word_positions(&self, word: &str) -> Vec<Position> {
+        match self.placements.get(word) {
+            Some(placement) => placement.get_positions(word.chars().count()),
+            None => vec![],
+        }
+    }
+
+    pub fn get_word_length_max() -> usize {
+        PUZZLE_SIZE_MAX
     }
 
     pub fn get_field_size() -> usize {
@@ -307,29 +466,87 @@ impl Puzzle {
     }
 
     pub fn get_description_line(&self) -> String {
-        format!("Puzzle: word count = {}; size = {}; {}; placement count = {}, intersection score = {}",
+        format!("Puzzle: word count = {}; size = {}; {}; placement count = {}, intersection score = {}; char map size = {}",
                 fc(self.words.len()),
                 fc(self.bounds.get_size()),
                 &self.bounds,
                 fc(self.placements.len()),
-                fc(self.get_intersection_score()))
+                fc(self.get_intersection_score()),
+                fc(self.char_map.len()))
+    }
+
+    /// Fill the empty cells with random letters. With forbid_contained_words, the letters are drawn
+    /// again whenever they spell another copy of a word, and if that keeps happening the grid is
+    /// left as it was and it's an error.
+    pub fn random_fill_optional(&mut self) -> Result<(), String> {
+        if self.is_random_filled {
+            return Ok(());
+        }
+        for _ in 0..RANDOM_FILL_TRY_COUNT_MAX {
+            self.fill_empty_cells(random_char);
+            if !self.settings.forbid_contained_words || self.find_ambiguous_words().is_empty() {
+                self.is_random_filled = true;
+                return Ok(());
+            }
+        }
+        let ambiguous_words = self.find_ambiguous_words();
+        self.fill_empty_cells(|| NO_CHAR);
+        Err(format!("Unable to fill the grid without spelling another copy of a word after {} tries. Found more than once: {}.", RANDOM_FILL_TRY_COUNT_MAX, ambiguous_words.join(", ")))
     }
 
-    pub fn random_fill_optional(&mut self) {
-        if !self.is_random_filled {
-            for y in self.bounds.get_y_min()..=self.bounds.get_y_max() {
-                for x in self.bounds.get_x_min()..=self.bounds.get_x_max() {
-                    let cell = self.get_cell_mut_xy(x, y);
-                    if cell.char == NO_CHAR {
-                        cell.char = random_char();
+    fn fill_empty_cells(&mut self, get_char: fn() -> char) {
+        for y in self.bounds.get_y_min()..=self.bounds.get_y_max() {
+            for x in self.bounds.get_x_min()..=self.bounds.get_x_max() {
+                let cell = &mut self.grid[&Position::new(x, y)];
+                if cell.word_count == 0 {
+                    cell.char = get_char();
+                }
+            }
+        }
+    }
+
+    /// The number of distinct places in the grid where the word can be read in any of the eight
+    /// directions.
+    pub fn count_occurrences(&self, word: &str) -> usize {
+        let chars = word.chars().collect::<Vec<_>>();
+        if chars.is_empty() {
+            return 0;
+        }
+        let mut found: Vec<Vec<Position>> = vec![];
+        for y in self.bounds.get_y_min()..=self.bounds.get_y_max() {
+            for x in self.bounds.get_x_min()..=self.bounds.get_x_max() {
+                if self.get_char_xy(x, y) != chars[0] {
+                    continue;
+                }
+                for direction in DIRECTIONS.iter() {
+                    let mut positions = match self.grid.get_line_positions(&Position::new(x, y), *direction, chars.len()) {
+                        Some(positions) => positions,
+                        None => continue,
+                    };
+                    let is_match = positions.iter().zip(chars.iter())
+                        .all(|(pos, char)| self.bounds.contains(pos) && self.get_char(pos) == *char);
+                    if is_match {
+                        // A palindrome reads the same both ways over the same cells. Count it once.
+                        positions.sort_by_key(|pos| (pos.get_y(), pos.get_x()));
+                        if !found.contains(&positions) {
+                            found.push(positions);
+                        }
                     }
                 }
             }
-            self.is_random_filled = true;
         }
+        found.len()
+    }
+
+    /// Words that can be found in more than one place.
+    pub fn find_ambiguous_words(&self) -> Vec<String> {
+        self.words.iter()
+            .filter(|word| self.count_occurrences(word) > 1)
+            .cloned()
+            .collect()
     }
 
-    pub fn print(&self, show_placements: bool, show_puzzle: bool) {
+    pub fn print(&self, show_placements: bool, show_char_map: bool, show_puzzle: bool) {
         println!("\n{}", self.get_description_line());
         if show_placements {
             println!("\tPlacements:");
@@ -337,36 +554,59 @@ impl Puzzle {
                 println!("\t\t\"{}\" at {}.", word, placement);
             }
         }
+        if show_char_map {
+            println!("\tChar Map:");
+            for (char, positions) in self.char_map.iter() {
+                let positions_desc = positions.iter().map(|pos| pos.to_string()).join(", ");
+                println!("\t\t\"{}\" at {}.", char, positions_desc);
+            }
+        }
         if show_puzzle {
             self.print_puzzle();
         }
     }
 
     pub fn print_all(&self) {
-        self.print(true, true);
+        self.print(true, true, true);
     }
 
     pub fn print_puzzle(&self) {
-        println!();
+        println!("\n{}", self.get_puzzle_string());
+    }
+
+    pub fn get_puzzle_string(&self) -> String {
+        let mut s = "".to_string();
         for y in self.bounds.get_y_min()..=self.bounds.get_y_max() {
-            let line = (self.bounds.get_x_min()..=self.bounds.get_x_max()).into_iter()
+            let line = (self.bounds.get_x_min()..=self.bounds.get_x_max())
                 .map(|x| self.get_char_xy(x, y).to_uppercase())
                 .join(" ");
-            println!("{}", line);
+            s.push_str(&format!("{}\n", line));
         }
-        println!();
+        s
+    }
+
+    pub fn get_word_bank_string(&self) -> String {
+        self.words.iter()
+            .sorted()
+            .map(|word| word.to_uppercase())
+            .join("\n") + "\n"
+    }
+
+    pub fn print_for_excel(&mut self, style: ExcelStyle) -> Result<(), String> {
+        println!("\n{}", self.get_excel_string(style)?);
+        Ok(())
     }
 
-    pub fn print_for_excel(&mut self, style: ExcelStyle) {
-        self.random_fill_optional();
+    pub fn get_excel_string(&mut self, style: ExcelStyle) -> Result<String, String> {
+        self.random_fill_optional()?;
         let offset_right = 100;
         let extra_tabs = "\t".repeat(offset_right - self.bounds.get_x_size());
-        println!();
+        let mut s = "".to_string();
         for y in self.bounds.get_y_min()..=self.bounds.get_y_max() {
             let mut line_left_half = "".to_string();
             let mut line_right_half = "".to_string();
             for x in self.bounds.get_x_min()..=self.bounds.get_x_max() {
-                let cell = self.get_cell_xy(x, y);
+                let cell = &self.grid[&Position::new(x, y)];
                 line_left_half.push_str(&format!("{}\t", cell.char.to_uppercase()));
                 let right_part = match style {
                     ExcelStyle::Density => cell.word_count.to_string(),
@@ -379,9 +619,9 @@ impl Puzzle {
                 };
                 line_right_half.push_str(&format!("{}\t", right_part));
             }
-            println!("{}{}{}", line_left_half, extra_tabs, line_right_half);
+            s.push_str(&format!("{}{}{}\n", line_left_half, extra_tabs, line_right_half));
         }
-        println!();
+        Ok(s)
     }
 }
 
@@ -390,14 +630,59 @@ impl Cell {
         Self {
             char: NO_CHAR,
             word_count: 0,
-            is_word_start: false
+            is_word_start: false,
+            has_n_s: false,
+            has_ne_sw: false,
+            has_e_w: false,
+            has_se_nw: false,
+            adjacent_counts: [0; 4],
+        }
+    }
+
+    #[inline]
+    pub fn get_char(&self) -> char {
+        self.char
+    }
+
+    /// The number of words that pass through this cell.
+    #[inline]
+    pub fn get_word_count(&self) -> usize {
+        self.word_count
+    }
+
+    #[inline]
+    pub fn is_word_start(&self) -> bool {
+        self.is_word_start
+    }
+
+    pub fn has_direction_conflict(&self, direction: &Direction) -> bool {
+        match direction {
+            Direction::N | Direction::S => self.has_n_s,
+            Direction::NE | Direction::SW => self.has_ne_sw,
+            Direction::E | Direction::W => self.has_e_w,
+            Direction::SE | Direction::NW => self.has_se_nw,
+        }
+    }
+
+    pub fn set_direction_flag(&mut self, direction: &Direction) {
+        match direction {
+            Direction::N | Direction::S => self.has_n_s = true,
+            Direction::NE | Direction::SW => self.has_ne_sw = true,
+            Direction::E | Direction::W => self.has_e_w = true,
+            Direction::SE | Direction::NW => self.has_se_nw = true,
         }
     }
 }
 
+impl Default for Cell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Display for Cell {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "[Cell: char = '{}']", self.char)
+        write!(f, "[Cell: char = '{}'; has_n_s = {}; has_ne_sw = {}; has_e_w = {}; has_se_nw = {}]", self.char, self.has_n_s, self.has_ne_sw, self.has_e_w, self.has_se_nw)
     }
 }
 
@@ -414,6 +699,22 @@ impl Placement {
         }
     }
 
+    pub fn get_position(&self) -> &Position {
+        &self.position
+    }
+
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn get_positions(&self, word_length: usize) -> Vec<Position> {
+        (0..word_length).filter_map(|steps| self.position.offset(self.direction, steps)).collect()
+    }
+
+    fn get_end_position(&self, word_length: usize) -> Position {
+        Puzzle::get_end_position(&self.position, &self.direction, word_length)
+    }
+
     fn get_intersection_score(&self) -> usize {
         if self.intersection_count == 0 {
             0
@@ -434,45 +735,6 @@ impl Display for Placement {
     }
 }
 
-impl Position {
-    fn new(x: usize, y: usize) -> Self {
-        assert!(x < PUZZLE_SIZE_MAX * FIELD_SIZE_MULT);
-        assert!(y < PUZZLE_SIZE_MAX * FIELD_SIZE_MULT);
-        Self {
-            x,
-            y
-        }
-    }
-
-    #[inline]
-    fn apply_offset(&mut self, offset: &Offset) {
-        self.x = (self.x as isize + offset[0]) as usize;
-        self.y = (self.y as isize + offset[1]) as usize;
-    }
-
-    fn back_to_word_start_optional(&self, char_index: usize, direction: &Direction, field_size: usize) -> Option<Self> {
-        // We're starting at the proposed intersection of a new word with an existing word at some
-        // character in the new word. We need to return the position of the first character of the
-        // new word.
-        let char_index = char_index as isize;
-        let field_size = field_size as isize;
-        let offset = direction.get_offset();
-        let x = self.x as isize - (char_index * offset[0]);
-        let y = self.y as isize - (char_index * offset[1]);
-        if x > 0 && x < field_size && y > 0 && y < field_size {
-            Some(Self::new(x as usize, y as usize))
-        } else {
-            None
-        }
-    }
-}
-
-impl Display for Position {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "({}, {})", fc(self.x), fc(self.y))
-    }
-}
-
 impl Bounds {
     fn new(top_left: Position, bottom_right: Position) -> Self {
         let bounds = Self {
@@ -484,43 +746,47 @@ impl Bounds {
     }
 
     #[inline]
-    fn get_size(&self) -> usize {
+    pub fn get_size(&self) -> usize {
         let x_size = (self.get_x_max() - self.get_x_min()) + 1;
         let y_size = (self.get_y_max() - self.get_y_min()) + 1;
         x_size.max(y_size)
     }
 
     #[inline]
-    fn get_x_size(&self) -> usize {
+    pub fn get_x_size(&self) -> usize {
         (self.get_x_max() - self.get_x_min()) + 1
     }
 
     #[inline]
-    fn get_x_min(&self) -> usize {
-        self.top_left.x
+    pub fn get_x_min(&self) -> usize {
+        self.top_left.get_x()
+    }
+
+    #[inline]
+    pub fn get_x_max(&self) -> usize {
+        self.bottom_right.get_x()
     }
 
     #[inline]
-    fn get_x_max(&self) -> usize {
-        self.bottom_right.x
+    pub fn get_y_min(&self) -> usize {
+        self.top_left.get_y()
     }
 
     #[inline]
-    fn get_y_min(&self) -> usize {
-        self.top_left.y
+    pub fn get_y_max(&self) -> usize {
+        self.bottom_right.get_y()
     }
 
     #[inline]
-    fn get_y_max(&self) -> usize {
-        self.bottom_right.y
+    pub fn contains(&self, position: &Position) -> bool {
+        position.get_x() >= self.get_x_min() && position.get_x() <= self.get_x_max()
+            && position.get_y() >= self.get_y_min() && position.get_y() <= self.get_y_max()
     }
 
     #[inline]
     fn apply_position(&mut self, position: &Position) {
-        self.top_left.x = self.top_left.x.min(position.x);
-        self.bottom_right.x = self.bottom_right.x.max(position.x);
-        self.top_left.y = self.top_left.y.min(position.y);
-        self.bottom_right.y = self.bottom_right.y.max(position.y);
+        self.top_left = Position::new(self.get_x_min().min(position.get_x()), self.get_y_min().min(position.get_y()));
+        self.bottom_right = Position::new(self.get_x_max().max(position.get_x()), self.get_y_max().max(position.get_y()));
     }
 
     fn invariant(&self) {
@@ -535,63 +801,23 @@ impl Display for Bounds {
     }
 }
 
-impl Direction {
-    fn get_offset(&self) -> Offset {
-        match self {
-            Direction::N => [0, -1],
-            Direction::NE => [1, -1],
-            Direction::E => [1, 0],
-            Direction::SE => [1, 1],
-            Direction::S => [0, 1],
-            Direction::SW => [-1, 1],
-            Direction::W => [-1, 0],
-            Direction::NW => [-1, -1],
-        }
-    }
-
-    fn opposite(&self) -> Self {
-        match self {
-            Direction::N => Direction::S,
-            Direction::NE => Direction::SW,
-            Direction::E => Direction::W,
-            Direction::SE => Direction::NW,
-            Direction::S => Direction::N,
-            Direction::SW => Direction::NE,
-            Direction::W => Direction::E,
-            Direction::NW => Direction::SE,
-        }
-    }
-
-    fn get_variant_name(&self) -> &str {
-        match self {
-            Direction::N => "N",
-            Direction::NE => "NE",
-            Direction::E => "E",
-            Direction::SE => "SE",
-            Direction::S => "S",
-            Direction::SW => "SW",
-            Direction::W => "W",
-            Direction::NW => "NW",
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            expansion: 0.2,
+            directions: DIRECTIONS.to_vec(),
+            try_count_max: 10,
+            create_seconds_max: None,
+            candidate_search: CandidateSearch::IntersectionFirst,
+            ranking: Ranking::Compact,
+            forbid_contained_words: false,
+            prevent_same_axis_overlap: true,
         }
     }
 }
 
-impl PartialEq for Direction {
-    fn eq(&self, other: &Self) -> bool {
-        self.get_variant_name() == other.get_variant_name()
-    }
-}
-
-impl Eq for Direction {}
-
-impl Display for Direction {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", self.get_variant_name())
-    }
-}
-
 pub fn random_char() -> char {
-    let ascii = thread_rng().gen_range(ASCII_A_LOWERCASE..ASCII_A_LOWERCASE + 26);
+    let ascii = rng().gen_range(ASCII_A_LOWERCASE..ASCII_A_LOWERCASE + 26);
     ascii as char
 }
 
@@ -599,15 +825,17 @@ pub fn main() {
     let words = word_list::WORDS_1;
     // let words = word_list::WORDS_4;
     // let words = word_list::ALL_SECOND_GRADE;
-    let expansion = 0.2;
-    // let directions = None;
-    // let directions = Some(vec![Direction::E, Direction::S]);
-    let directions = Some(vec![Direction::E, Direction::SE, Direction::S]);
-    // let directions = Some(vec![Direction::NE, Direction::E, Direction::SE, Direction::S]);
-    // let directions = Some(vec![Direction::NW]);
-    let try_count_max = 10;
-    let mut puzzle = Puzzle::find_best_puzzle(&slice_str_to_strings(&words.to_vec()), expansion, directions, try_count_max);
+    let settings = Settings {
+        expansion: 0.2,
+        // directions: vec![Direction::E, Direction::S],
+        directions: vec![Direction::E, Direction::SE, Direction::S],
+        // directions: vec![Direction::NE, Direction::E, Direction::SE, Direction::S],
+        // directions: vec![Direction::NW],
+        try_count_max: 10,
+        ..Default::default()
+    };
+    let mut puzzle = Puzzle::find_best_puzzle(&slice_str_to_strings(&words), &settings).unwrap();
     puzzle.print_all();
-    puzzle.print_for_excel(ExcelStyle::Reveal);
-    puzzle.print_for_excel(ExcelStyle::Density);
+    puzzle.print_for_excel(ExcelStyle::Reveal).unwrap();
+    puzzle.print_for_excel(ExcelStyle::Density).unwrap();
 }
@@ -0,0 +1,60 @@
+use word_games::*;
+use word_games::book;
+use word_games::word_list::{FileFormat, WordList};
+
+fn sorted(words: &[String]) -> Vec<String> {
+    let mut words = words.to_vec();
+    words.sort();
+    words
+}
+
+#[test]
+fn random_groups_differ_in_size_by_at_most_one() {
+    random::set_seed(0);
+    let words = slice_str_to_strings(&word_list::WORDS_1);
+    let groups = book::partition_random(&words, 3).unwrap();
+    let titles = groups.iter().map(|(title, _)| title.as_str()).collect::<Vec<_>>();
+    assert_eq!(titles, vec!["Mixed Words 1", "Mixed Words 2", "Mixed Words 3"]);
+    let sizes = groups.iter().map(|(_, group)| group.len()).collect::<Vec<_>>();
+    assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 1, "{:?}", sizes);
+    let dealt = groups.into_iter().flat_map(|(_, group)| group).collect::<Vec<_>>();
+    assert_eq!(sorted(&dealt), sorted(&words));
+}
+
+// "café" and "naïve" have more bytes than letters, so they'd land with the longer words if the
+// bytes were counted.
+#[test]
+fn length_groups_go_from_shortest_to_longest_by_letter_count() {
+    random::set_seed(0);
+    let words = slice_str_to_strings(&["horse", "café", "ox", "naïve", "dog", "bird", "cat"]);
+    let groups = book::partition_by_length(&words, 3).unwrap();
+    let groups = groups.into_iter().map(|(title, group)| (title, sorted(&group))).collect::<Vec<_>>();
+    assert_eq!(groups, vec![
+        ("2 to 3 Letter Words".to_string(), slice_str_to_strings(&["cat", "dog", "ox"])),
+        ("4-Letter Words".to_string(), slice_str_to_strings(&["bird", "café"])),
+        ("5-Letter Words".to_string(), slice_str_to_strings(&["horse", "naïve"])),
+    ]);
+}
+
+#[test]
+fn tag_groups_keep_the_biggest_tags() {
+    let list = WordList::parse("test", "word,tags\ncat,pet;animal\ndog,pet;animal\ncow,animal\nrose,plant\n", FileFormat::Csv).unwrap();
+    let groups = book::partition_by_tag(&list, 2).unwrap();
+    assert_eq!(groups, vec![
+        ("animal".to_string(), slice_str_to_strings(&["cat", "dog", "cow"])),
+        ("pet".to_string(), slice_str_to_strings(&["cat", "dog"])),
+    ]);
+    assert!(book::partition_by_tag(&list, 4).is_err());
+    assert!(book::partition_by_tag(&list, 0).is_err());
+    assert!(book::partition_by_tag(&WordList::new("test", &["cat"]), 1).is_err());
+}
+
+#[test]
+fn group_count_must_fit_the_words() {
+    let words = slice_str_to_strings(&["cat", "dog"]);
+    assert!(book::partition_random(&words, 0).is_err());
+    assert!(book::partition_random(&words, 3).is_err());
+    assert!(book::partition_by_length(&words, 0).is_err());
+    assert!(book::partition_by_length(&words, 3).is_err());
+    assert_eq!(book::partition_by_length(&words, 2).unwrap().len(), 2);
+}
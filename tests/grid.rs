@@ -0,0 +1,113 @@
+use word_games::grid::{Direction, Grid, Position, DIRECTIONS};
+
+// A 4 wide by 3 high grid:
+//
+//     a b c d
+//     e f g h
+//     i j k l
+fn letters() -> Grid<char> {
+    Grid::from_rows(vec!["abcd".chars().collect(), "efgh".chars().collect(), "ijkl".chars().collect()]).unwrap()
+}
+
+fn line(grid: &Grid<char>, x: usize, y: usize, direction: Direction, length: usize) -> Option<String> {
+    grid.get_line(&Position::new(x, y), direction, length).map(|line| line.collect())
+}
+
+#[test]
+fn get_outside_the_grid_is_none() {
+    let mut grid = letters();
+    assert_eq!(grid.get_xy(3, 2), Some(&'l'));
+    assert_eq!(grid.get_xy(4, 0), None);
+    assert_eq!(grid.get_xy(0, 3), None);
+    assert_eq!(grid.get_xy(usize::MAX, usize::MAX), None);
+    assert_eq!(grid.get_mut_xy(4, 2), None);
+    *grid.get_mut_xy(1, 1).unwrap() = 'F';
+    assert_eq!(grid.get_xy(1, 1), Some(&'F'));
+}
+
+#[test]
+#[should_panic]
+fn index_outside_the_grid_panics() {
+    let grid = letters();
+    let _ = grid[&Position::new(0, 3)];
+}
+
+#[test]
+fn lines_run_in_every_direction() {
+    let grid = letters();
+    let from_f = DIRECTIONS.iter()
+        .map(|direction| line(&grid, 1, 1, *direction, 2).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(from_f, vec!["fb", "fc", "fg", "fk", "fj", "fi", "fe", "fa"]);
+    assert_eq!(line(&grid, 0, 0, Direction::E, 4).unwrap(), "abcd");
+    assert_eq!(line(&grid, 3, 2, Direction::W, 4).unwrap(), "lkji");
+    assert_eq!(line(&grid, 3, 0, Direction::S, 3).unwrap(), "dhl");
+    assert_eq!(line(&grid, 0, 2, Direction::N, 3).unwrap(), "iea");
+    assert_eq!(line(&grid, 0, 0, Direction::SE, 3).unwrap(), "afk");
+    assert_eq!(line(&grid, 3, 2, Direction::NW, 3).unwrap(), "lgb");
+    assert_eq!(line(&grid, 3, 0, Direction::SW, 3).unwrap(), "dgj");
+    assert_eq!(line(&grid, 0, 2, Direction::NE, 3).unwrap(), "ifc");
+}
+
+#[test]
+fn lines_off_the_grid_are_none() {
+    let grid = letters();
+    assert_eq!(line(&grid, 0, 0, Direction::E, 5), None);
+    assert_eq!(line(&grid, 0, 0, Direction::W, 2), None);
+    assert_eq!(line(&grid, 0, 0, Direction::N, 2), None);
+    assert_eq!(line(&grid, 0, 0, Direction::SE, 4), None);
+    assert_eq!(line(&grid, 4, 0, Direction::E, 1), None);
+    assert_eq!(line(&grid, 0, 0, Direction::E, 1).unwrap(), "a");
+    assert_eq!(line(&grid, 0, 0, Direction::E, 0).unwrap(), "");
+    assert!(grid.get_line_positions(&Position::new(1, 0), Direction::S, 4).is_none());
+    assert_eq!(grid.get_line_positions(&Position::new(1, 0), Direction::S, 3).unwrap(),
+        vec![Position::new(1, 0), Position::new(1, 1), Position::new(1, 2)]);
+}
+
+#[test]
+fn lines_to_the_edge() {
+    let grid = letters();
+    let to_edge = |x, y, direction| grid.get_line_to_edge(&Position::new(x, y), direction).collect::<String>();
+    assert_eq!(to_edge(1, 1, Direction::E), "fgh");
+    assert_eq!(to_edge(1, 1, Direction::SE), "fk");
+    assert_eq!(to_edge(1, 1, Direction::NW), "fa");
+    assert_eq!(to_edge(0, 2, Direction::NE), "ifc");
+    assert_eq!(to_edge(4, 0, Direction::E), "");
+}
+
+#[test]
+fn neighbors_stay_on_the_grid() {
+    let grid = letters();
+    assert_eq!(grid.get_neighbors(&Position::new(1, 1)).len(), 8);
+    let corner = grid.get_neighbors(&Position::new(0, 0)).into_iter()
+        .map(|(direction, position)| (direction, grid[&position]))
+        .collect::<Vec<_>>();
+    assert_eq!(corner, vec![(Direction::E, 'b'), (Direction::SE, 'f'), (Direction::S, 'e')]);
+    assert_eq!(grid.get_neighbor(&Position::new(3, 1), Direction::E), None);
+}
+
+#[test]
+fn rows_and_columns_of_a_non_square_grid() {
+    let grid = letters();
+    assert_eq!((grid.get_width(), grid.get_height()), (4, 3));
+    assert_eq!(grid.get_row(2), &['i', 'j', 'k', 'l']);
+    assert_eq!(grid.get_column(3).collect::<String>(), "dhl");
+    assert_eq!(grid.get_column(4).count(), 0);
+    assert_eq!(grid.get_rows().count(), 3);
+    assert_eq!(grid.iter().last(), Some((Position::new(3, 2), &'l')));
+    assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+}
+
+#[test]
+fn transforms_of_a_non_square_grid() {
+    let grid = letters();
+    assert_eq!(grid.rotate_clockwise().to_string(), "i e a\nj f b\nk g c\nl h d\n");
+    assert_eq!(grid.rotate_counterclockwise().to_string(), "d h l\nc g k\nb f j\na e i\n");
+    assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    assert_eq!(grid.transpose().to_string(), "a e i\nb f j\nc g k\nd h l\n");
+    assert_eq!(grid.flip_horizontal().to_string(), "d c b a\nh g f e\nl k j i\n");
+    assert_eq!(grid.flip_vertical().to_string(), "i j k l\ne f g h\na b c d\n");
+    assert_eq!(grid.crop(&Position::new(1, 1), &Position::new(3, 2)).unwrap().to_string(), "f g h\nj k l\n");
+    assert!(grid.crop(&Position::new(1, 1), &Position::new(4, 2)).is_none());
+    assert!(grid.crop(&Position::new(2, 1), &Position::new(1, 2)).is_none());
+}
@@ -0,0 +1,701 @@
+use word_games::*;
+use word_games::jumble::cloze::{self, Cloze};
+use word_games::jumble::dictionary::Dictionary;
+use word_games::jumble::phrase_list::PhraseList;
+use word_games::jumble::puzzle::{self, Difficulty, Mode, Phrase, Puzzle, Settings};
+use word_games::jumble::riddle::{self, Riddle};
+use word_games::jumble::worksheet;
+use word_games::word_list::FileFormat;
+
+fn blanked(phrase: &str, blank_indexes: &[usize]) -> Phrase {
+    let mut phrase = Phrase::new(phrase).unwrap();
+    phrase.set_blank_indexes(blank_indexes).unwrap();
+    phrase
+}
+
+#[test]
+fn count_solutions_unique() {
+    let dictionary = Dictionary::new(&["cat", "dog"]);
+    assert_eq!(blanked("cat", &[0, 1]).count_solutions(&dictionary, 10).unwrap(), 1);
+    assert_eq!(blanked("dog cat", &[0, 4]).count_solutions(&dictionary, 10).unwrap(), 1);
+}
+
+// The built-in lists don't have "tops", so the "p" and "t" of "pots" seem to fit back in only
+// one way until a word list with it is loaded.
+#[test]
+fn count_solutions_uses_a_loaded_dictionary() {
+    let phrase = blanked("pots", &[0, 2]);
+    let mut dictionary = Dictionary::built_in();
+    dictionary.insert("pots");
+    assert_eq!(phrase.count_solutions(&dictionary, 10).unwrap(), 1);
+    let path = std::env::temp_dir().join("word_games_count_solutions_dictionary.txt");
+    std::fs::write(&path, "tops\n").unwrap();
+    let loaded = Dictionary::read_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    dictionary.extend(&loaded.unwrap().get_words().iter().collect::<Vec<_>>());
+    assert_eq!(phrase.count_solutions(&dictionary, 10).unwrap(), 2);
+}
+
+#[test]
+fn unique_needs_a_dictionary() {
+    let result = cli::run(["word_games", "jumble", "--list", "WORDS_1", "--unique"]);
+    assert!(result.unwrap_err().contains("--dictionary"));
+}
+
+#[test]
+fn count_solutions_ambiguous() {
+    let dictionary = Dictionary::new(&["cat", "act"]);
+    let phrase = blanked("cat", &[0, 1]);
+    assert_eq!(phrase.count_solutions(&dictionary, 10).unwrap(), 2);
+    assert_eq!(phrase.count_solutions(&dictionary, 1).unwrap(), 1);
+    // The letters can swap between words as well as within one.
+    let dictionary = Dictionary::new(&["bat", "hat", "cat"]);
+    assert_eq!(blanked("bat hat", &[0, 4]).count_solutions(&dictionary, 10).unwrap(), 2);
+}
+
+#[test]
+fn count_solutions_ignores_case() {
+    let dictionary = Dictionary::new(&["tot"]);
+    assert_eq!(blanked("tot", &[0, 2]).count_solutions(&dictionary, 10).unwrap(), 1);
+    assert_eq!(blanked("Tot", &[0, 2]).count_solutions(&dictionary, 10).unwrap(), 1);
+    let dictionary = Dictionary::new(&["Cat", "act"]);
+    assert_eq!(blanked("Cat", &[0, 1]).count_solutions(&dictionary, 10).unwrap(), 2);
+}
+
+#[test]
+fn count_solutions_needs_dictionary_words() {
+    let dictionary = Dictionary::new(&["dog"]);
+    assert_eq!(blanked("cat", &[0, 1]).count_solutions(&dictionary, 10).unwrap(), 0);
+}
+
+#[test]
+fn count_solutions_across_many_words() {
+    // Prefixes that don't start any word are cut off early, so a blank at the start of each of
+    // many words doesn't mean trying every order of the letters.
+    let dictionary = Dictionary::built_in();
+    let words = word_list::ALL_SECOND_GRADE.iter().take(40).join(" ");
+    let word_starts = Phrase::new(&words).unwrap().get_word_ranges().into_iter().map(|(start, _)| start).collect::<Vec<_>>();
+    assert!(blanked(&words, &word_starts).count_solutions(&dictionary, 2).unwrap() >= 1);
+}
+
+#[test]
+fn dictionary_prefixes() {
+    let dictionary = Dictionary::new(&["cat", "Dog"]);
+    assert!(dictionary.contains_prefix("ca"));
+    assert!(dictionary.contains_prefix("cat"));
+    assert!(dictionary.contains_prefix("DO"));
+    assert!(!dictionary.contains_prefix("cats"));
+    assert!(!dictionary.contains_prefix("b"));
+}
+
+#[test]
+fn shared_bank_letters_can_swap_between_phrases() {
+    let dictionary = Dictionary::new(&["bat", "hat"]);
+    // Each phrase alone has one answer, but with "b" and "h" pooled either can take either.
+    let phrases = vec![blanked("bat", &[0]), blanked("hat", &[0])];
+    assert_eq!(phrases[0].count_solutions(&dictionary, 10).unwrap(), 1);
+    assert_eq!(puzzle::count_shared_solutions(&phrases, &dictionary, 10).unwrap(), 2);
+    let phrases = vec![blanked("Bat", &[0]), blanked("hat", &[0])];
+    assert_eq!(puzzle::count_shared_solutions(&phrases, &dictionary, 10).unwrap(), 2);
+    // Blanking the "a"s instead leaves only one way to fill them in.
+    let phrases = vec![blanked("bat", &[1]), blanked("hat", &[1])];
+    assert_eq!(puzzle::count_shared_solutions(&phrases, &dictionary, 10).unwrap(), 1);
+}
+
+#[test]
+fn shared_bank_ignores_case() {
+    let dictionary = Dictionary::new(&["tot", "cat"]);
+    let phrases = vec![blanked("Tot", &[0, 2]), blanked("cat", &[0])];
+    assert_eq!(puzzle::count_shared_solutions(&phrases, &dictionary, 10).unwrap(), 1);
+}
+
+#[test]
+fn shared_bank_puzzles_have_one_solution() {
+    let settings = Settings {
+        shared_letter_bank: true,
+        ..Default::default()
+    };
+    for seed in 0..20 {
+        random::set_seed(seed);
+        let mut puzzle = Puzzle::new(&["bat", "hat", "cat", "the dog ran"], &settings).unwrap();
+        puzzle.build().unwrap();
+        assert_eq!(puzzle.count_shared_solutions(10).unwrap(), 1);
+        assert_eq!(puzzle.get_letter_bank().len(), puzzle.get_phrases().iter().map(|phrase| phrase.get_missing_letters().len()).sum::<usize>());
+    }
+}
+
+#[test]
+fn worksheet_lists_each_category_separately() {
+    let list = PhraseList::parse("phrase,category\ncat,Pets\ndog,Pets\nred fox,Wild\nbig owl,Wild\nthe sun\n", FileFormat::Csv).unwrap();
+    let mut puzzle = Puzzle::from_phrase_list(&list, &Settings::default()).unwrap();
+    puzzle.build().unwrap();
+    let html = worksheet::get_html_string(&puzzle, "Animals");
+    assert_eq!(html.matches("<ol").count(), html.matches("</ol>").count());
+    // The phrase without a category sorts first and the numbering runs on across the lists.
+    let questions = &html[..html.find("<div class=\"answer-key\">").unwrap()];
+    assert_eq!(questions.matches("<ol ").count(), 3);
+    assert!(questions.contains("<ol start=\"1\">"));
+    assert!(questions.contains("</ol>\n<div class=\"category\">Pets</div>\n<ol start=\"2\">"));
+    assert!(questions.contains("</ol>\n<div class=\"category\">Wild</div>\n<ol start=\"4\">"));
+    // Nothing but list items goes directly inside a list.
+    for list in html.split("<ol ").skip(1) {
+        let items = &list[list.find('>').unwrap() + 1..list.find("</ol>").unwrap()];
+        assert!(!items.contains("class=\"category\""));
+        assert!(items.trim_start().starts_with("<li>"));
+    }
+}
+
+// The questions and the answer key, split where the answer key starts.
+fn worksheet_parts(puzzle: &Puzzle, title: &str) -> (String, String) {
+    let html = worksheet::get_html_string(puzzle, title);
+    let split = html.find("<div class=\"answer-key\">").unwrap();
+    (html[..split].to_string(), html[split..].to_string())
+}
+
+#[test]
+fn worksheet_boxes_are_empty_until_the_answer_key() {
+    let mut puzzle = Puzzle::new(&["zebra quilt"], &Settings::default()).unwrap();
+    puzzle.set_blank_indexes(&[vec![0, 6, 9]]).unwrap();
+    let (questions, answer_key) = worksheet_parts(&puzzle, "Animals");
+    assert_eq!(questions.matches("class=\"box\"").count(), 3);
+    assert_eq!(questions.matches("<span class=\"box\"></span>").count(), 3);
+    assert!(questions.contains("<div class=\"missing\">z q l</div>"));
+    assert_eq!(answer_key.matches("class=\"box\"").count(), 3);
+    for letter in ["z", "q", "l"] {
+        assert!(answer_key.contains(&format!("<span class=\"box\">{}</span>", letter)), "{}", letter);
+    }
+    assert!(!answer_key.contains("class=\"missing\""));
+}
+
+#[test]
+fn worksheet_letter_bank_lists_the_missing_letters() {
+    let settings = Settings {
+        shared_letter_bank: true,
+        ..Default::default()
+    };
+    let mut puzzle = Puzzle::new(&["cat", "dog"], &settings).unwrap();
+    puzzle.set_blank_indexes(&[vec![0, 2], vec![1]]).unwrap();
+    let (questions, _) = worksheet_parts(&puzzle, "Animals");
+    let bank_start = questions.find("<div class=\"bank\">").unwrap() + "<div class=\"bank\">".len();
+    let bank = &questions[bank_start..bank_start + questions[bank_start..].find("</div>").unwrap()];
+    let expected = puzzle.get_phrases().iter().flat_map(|phrase| phrase.get_missing_letters().iter().cloned()).sorted().collect::<Vec<_>>();
+    assert_eq!(bank.split(' ').map(|letter| letter.to_string()).sorted().collect::<Vec<_>>(), expected);
+    assert!(!questions.contains("class=\"missing\""));
+}
+
+#[test]
+fn worksheet_items_are_numbered_with_the_answer_key_on_its_own_page() {
+    let mut puzzle = Puzzle::new(&["the cat sat", "a big dog", "red fox"], &Settings::default()).unwrap();
+    puzzle.build().unwrap();
+    let html = worksheet::get_html_string(&puzzle, "Animals");
+    let (questions, answer_key) = worksheet_parts(&puzzle, "Animals");
+    for part in [&questions, &answer_key] {
+        assert_eq!(part.matches("<ol start=\"1\">").count(), 1);
+        assert_eq!(part.matches("<li>").count(), 3);
+    }
+    assert!(html.contains(".answer-key { page-break-before: always; }"));
+    assert!(answer_key.starts_with("<div class=\"answer-key\">\n<h1>Animals - Answer Key</h1>"));
+    assert!(answer_key.ends_with("</div>\n</body>\n</html>\n"));
+}
+
+#[test]
+fn worksheet_escapes_titles_and_hints() {
+    let list = PhraseList::parse("phrase,hint\ncat,\"Says \"\"meow\"\" & <purrs>\"\n", FileFormat::Csv).unwrap();
+    let mut puzzle = Puzzle::from_phrase_list(&list, &Settings::default()).unwrap();
+    puzzle.build().unwrap();
+    let html = worksheet::get_html_string(&puzzle, "Cats & <Dogs> \"Fun\"");
+    assert!(html.contains("<title>Cats &amp; &lt;Dogs&gt; &quot;Fun&quot;</title>"));
+    assert!(html.contains("<h1>Cats &amp; &lt;Dogs&gt; &quot;Fun&quot;</h1>"));
+    assert_eq!(html.matches("<div class=\"hint\">Says &quot;meow&quot; &amp; &lt;purrs&gt;</div>").count(), 2);
+    assert!(!html.contains("<Dogs>") && !html.contains("<purrs>"));
+}
+
+#[test]
+fn easy_never_blanks_the_first_letter() {
+    let settings = Settings {
+        difficulty: Difficulty::Easy,
+        blank_count: 8,
+        ..Default::default()
+    };
+    for seed in 0..50 {
+        random::set_seed(seed);
+        let mut puzzle = Puzzle::new(&["the cat sat on a mat", "Zebras quietly jump!"], &settings).unwrap();
+        puzzle.build().unwrap();
+        for phrase in puzzle.get_phrases() {
+            assert_eq!(phrase.get_blank_indexes().len(), 8);
+            for (start, _) in phrase.get_word_ranges() {
+                assert!(!phrase.get_blank_indexes().contains(&start), "{} has a blank at {}", phrase.get_phrase(), start);
+            }
+        }
+    }
+}
+
+// In "cat strop", "c" could be "h" to make "hat" and "str" is a cluster of consonants. The other
+// four letters are neither, so hard blanks should mostly land on the first four.
+#[test]
+fn hard_favors_consonant_clusters_and_ambiguous_letters() {
+    let favored_share = |difficulty| {
+        let settings = Settings {
+            difficulty,
+            dictionary: Dictionary::new(&["hat"]),
+            ..Default::default()
+        };
+        let mut favored_count = 0;
+        let mut blank_count = 0;
+        for seed in 0..100 {
+            random::set_seed(seed);
+            let mut puzzle = Puzzle::new(&["cat strop"], &settings).unwrap();
+            puzzle.build().unwrap();
+            let blank_indexes = puzzle.get_phrases()[0].get_blank_indexes();
+            favored_count += blank_indexes.iter().filter(|index| [0, 4, 5, 6].contains(*index)).count();
+            blank_count += blank_indexes.len();
+        }
+        favored_count as f32 / blank_count as f32
+    };
+    let hard = favored_share(Difficulty::Hard);
+    let medium = favored_share(Difficulty::Medium);
+    assert!(hard > 0.65, "{}", hard);
+    assert!(hard > medium + 0.1, "hard {} medium {}", hard, medium);
+}
+
+#[test]
+fn blank_percent_is_relative_to_each_word() {
+    let blank_indexes = |phrase, percent| {
+        let settings = Settings {
+            blank_percent: Some(percent),
+            ..Default::default()
+        };
+        random::set_seed(1);
+        let mut puzzle = Puzzle::new(&[phrase], &settings).unwrap();
+        puzzle.build().map(|_| puzzle.get_phrases()[0].get_blank_indexes().clone())
+    };
+    // Five letters in each word, so each percent point is a twentieth of a blank per word.
+    let blank_count = |percent| blank_indexes("zebra quilt", percent).map(|indexes| indexes.len());
+    assert_eq!(blank_count(29), Ok(2));
+    assert_eq!(blank_count(30), Ok(4));
+    assert_eq!(blank_count(50), Ok(6));
+    assert_eq!(blank_count(1), Ok(2));
+    assert_eq!(blank_count(0), Ok(2));
+    // At least one letter of each word is always left showing.
+    assert_eq!(blank_count(100), Ok(8));
+    assert!(blank_count(101).is_err());
+    // Half of "go" is one blank and half of "elephants" rounds up to five.
+    let indexes = blank_indexes("go elephants", 50).unwrap();
+    assert_eq!(indexes.iter().filter(|index| **index < 2).count(), 1);
+    assert_eq!(indexes.iter().filter(|index| **index > 2).count(), 5);
+    // Too little of either word to round up to a blank, so the longer word gets both.
+    let indexes = blank_indexes("go elephants", 10).unwrap();
+    assert_eq!(indexes.len(), 2);
+    assert!(indexes.iter().all(|index| *index > 2), "{:?}", indexes);
+    // A blank count from the phrase list wins over the percentage.
+    let list = PhraseList::parse("phrase,blanks\nzebra quilt,6\n", FileFormat::Csv).unwrap();
+    let settings = Settings {
+        blank_percent: Some(20),
+        ..Default::default()
+    };
+    let mut puzzle = Puzzle::from_phrase_list(&list, &settings).unwrap();
+    puzzle.build().unwrap();
+    assert_eq!(puzzle.get_phrases()[0].get_blank_indexes().len(), 6);
+}
+
+#[test]
+fn items_are_numbered_like_the_answer_key() {
+    let list = PhraseList::parse("phrase,hint\nthe cat sat,Pets\nbig dog,\nred fox,Wild\n", FileFormat::Csv).unwrap();
+    let mut puzzle = Puzzle::from_phrase_list(&list, &Settings::default()).unwrap();
+    puzzle.build().unwrap();
+    let s = puzzle.get_string(true);
+    let answer_key = puzzle.get_answer_string();
+    for (index, phrase) in puzzle.get_phrases().iter().enumerate() {
+        let number = format!("{}. ", index + 1);
+        assert!(answer_key.contains(&format!("{}{}\n", number, phrase.get_phrase())));
+        // The phrase comes first, and the item's other lines are indented under it.
+        let item = &s[s.find(&format!("{}{}\n", number, phrase.get_phrase())).unwrap()..];
+        let item = &item[..item.find("\n\n").unwrap()];
+        assert!(item.lines().skip(1).all(|line| line.starts_with("   ") && !line.starts_with("    ")));
+        assert!(item.contains(&format!("\n   {}", phrase.get_jumble())));
+    }
+    assert!(s.contains("   Hint: Pets\n"));
+}
+
+fn blanks_puzzle(phrases: &[&str], settings: &Settings) -> Puzzle {
+    random::set_seed(1);
+    let mut puzzle = Puzzle::new(phrases, settings).unwrap();
+    puzzle.build().unwrap();
+    puzzle
+}
+
+// The answer with each blank filled with the letter from the phrase, or with "x" where replace
+// is true.
+fn fill_in(phrase: &Phrase, replace: &[bool]) -> String {
+    let mut graphemes = phrase.get_graphemes().clone();
+    for (blank_index, replace) in phrase.get_blank_indexes().iter().zip(replace.iter()) {
+        if *replace {
+            graphemes[*blank_index] = "x".to_string();
+        }
+    }
+    graphemes.concat()
+}
+
+#[test]
+fn check_exact_answers() {
+    let puzzle = blanks_puzzle(&["the cat sat", "a big dog"], &Settings::default());
+    let answers = puzzle.get_phrases().iter().map(|phrase| phrase.get_phrase().to_uppercase()).collect::<Vec<_>>();
+    let check = puzzle.check(&answers.iter().map(|answer| answer.as_str()).collect::<Vec<_>>()).unwrap();
+    assert_eq!(check.get_score(), 1.0);
+    assert_eq!(check.get_correct_count(), 2);
+    assert!(puzzle.check(&["the cat sat"]).is_err());
+}
+
+#[test]
+fn check_trims_the_phrase_and_the_answer() {
+    let puzzle = blanks_puzzle(&["  -e''bExE ", "\tthe cat sat\n"], &Settings::default());
+    assert_eq!(puzzle.get_phrases().iter().map(|phrase| phrase.get_phrase()).sorted().collect::<Vec<_>>(), vec!["-e''bExE", "the cat sat"]);
+    let answers = puzzle.get_phrases().iter().map(|phrase| format!(" {} ", phrase.get_phrase())).collect::<Vec<_>>();
+    let check = puzzle.check(&answers.iter().map(|answer| answer.as_str()).collect::<Vec<_>>()).unwrap();
+    assert_eq!(check.get_score(), 1.0);
+}
+
+#[test]
+fn check_partial_answers() {
+    let settings = Settings {
+        blank_count: 4,
+        ..Default::default()
+    };
+    let puzzle = blanks_puzzle(&["zebra quilt"], &settings);
+    let phrase = &puzzle.get_phrases()[0];
+    let answer = fill_in(phrase, &[true, false, false, true]);
+    let check = puzzle.check(&[&answer]).unwrap();
+    assert_eq!(check.get_score(), 0.5);
+    assert_eq!(check.get_phrase_checks()[0].get_blanks(), &vec![false, true, true, false]);
+    assert_eq!(check.get_correct_count(), 0);
+    // An answer of the wrong length gets nothing.
+    assert_eq!(puzzle.check(&["zebra"]).unwrap().get_score(), 0.0);
+}
+
+#[test]
+fn check_alternative_answers() {
+    let settings = Settings {
+        dictionary: Dictionary::new(&["bat", "hat"]),
+        ..Default::default()
+    };
+    let mut puzzle = Puzzle::new(&["bat hat"], &settings).unwrap();
+    // Blank the "b" and the "h", which can trade places.
+    puzzle.set_blank_indexes(&[vec![0, 4]]).unwrap();
+    let check = puzzle.check(&["hat bat"]).unwrap();
+    assert!(check.get_phrase_checks()[0].is_alternative());
+    assert_eq!(check.get_score(), 1.0);
+    // Right letters, but not dictionary words.
+    let check = puzzle.check(&["bat bat"]).unwrap();
+    assert!(!check.get_phrase_checks()[0].is_alternative());
+    assert_eq!(check.get_score(), 0.5);
+}
+
+#[test]
+fn check_shared_letter_bank() {
+    let settings = Settings {
+        dictionary: Dictionary::new(&["bat", "hat", "cat"]),
+        shared_letter_bank: true,
+        ..Default::default()
+    };
+    let mut puzzle = Puzzle::new(&["bat", "hat", "cat"], &settings).unwrap();
+    puzzle.set_blank_indexes(&[vec![0], vec![0], vec![0]]).unwrap();
+    let answers = puzzle.get_phrases().iter().map(|phrase| phrase.get_phrase()).collect::<Vec<_>>();
+    assert_eq!(puzzle.check(&answers).unwrap().get_score(), 1.0);
+    // Each answer uses letters that are in the bank, but together they use one letter too many.
+    let answers = answers.iter().map(|_| "bat").collect::<Vec<_>>();
+    let check = puzzle.check(&answers).unwrap();
+    assert!(check.get_phrase_checks().iter().all(|phrase_check| !phrase_check.is_alternative()));
+    assert!(check.get_score() < 1.0);
+}
+
+#[test]
+fn check_shared_letter_bank_with_a_wrong_answer() {
+    let settings = Settings {
+        dictionary: Dictionary::new(&["bat", "hat", "cat"]),
+        shared_letter_bank: true,
+        ..Default::default()
+    };
+    // Whatever order the phrases are in, "hat" and "bat" fit in two of the blanks with letters
+    // from the bank, so a wrong third answer doesn't make them wrong too.
+    for seed in 0..10 {
+        random::set_seed(seed);
+        let mut puzzle = Puzzle::new(&["bat", "hat", "cat"], &settings).unwrap();
+        puzzle.set_blank_indexes(&[vec![0], vec![0], vec![0]]).unwrap();
+        assert_eq!(puzzle.get_letter_bank().iter().sorted().collect::<Vec<_>>(), vec!["b", "c", "h"]);
+        let check = puzzle.check(&["hat", "bat", "xat"]).unwrap();
+        assert_eq!(check.get_correct_count(), 2);
+        assert!((check.get_score() - 2.0 / 3.0).abs() < 1e-6);
+    }
+}
+
+fn cloze_jumble(word: &str, sentence: &str, settings: &cloze::Settings) -> Result<String, String> {
+    Cloze::new(&[(word, sentence)], settings).map(|cloze| cloze.get_items()[0].get_phrase().get_jumble().to_string())
+}
+
+#[test]
+fn cloze_blanks_the_whole_word() {
+    let settings = cloze::Settings::default();
+    assert_eq!(cloze_jumble("cat", "The catalog shows a Cat.", &settings).unwrap(), "The catalog shows a ___.");
+    assert!(cloze_jumble("cat", "The catalog came today.", &settings).is_err());
+    assert!(cloze_jumble("cat", "A bobcat ran by.", &settings).is_err());
+}
+
+#[test]
+fn cloze_allows_regular_endings() {
+    let settings = cloze::Settings::default();
+    assert_eq!(cloze_jumble("jump", "She jumped high.", &settings).unwrap(), "She ______ high.");
+    assert_eq!(cloze_jumble("run", "He is running late.", &settings).unwrap(), "He is _______ late.");
+    assert_eq!(cloze_jumble("make", "We are making soup.", &settings).unwrap(), "We are ______ soup.");
+    assert_eq!(cloze_jumble("happy", "She is happier now.", &settings).unwrap(), "She is _______ now.");
+    // An exact match wins over an earlier word with an ending.
+    assert_eq!(cloze_jumble("run", "Runs are fun, so run.", &settings).unwrap(), "Runs are fun, so ___.");
+    assert!(cloze_jumble("run", "The runway is long.", &settings).is_err());
+}
+
+#[test]
+fn cloze_matches_multi_word_entries() {
+    let settings = cloze::Settings::default();
+    assert_eq!(cloze_jumble("ice cream", "We ate ice cream today.", &settings).unwrap(), "We ate ___ _____ today.");
+    assert_eq!(cloze_jumble("Ice  Cream", "Ice creams melt.", &settings).unwrap(), "___ ______ melt.");
+    assert!(cloze_jumble("ice cream", "The ice was cold and the cream was sweet.", &settings).is_err());
+    let settings = cloze::Settings {
+        letter_blank_count: Some(20),
+    };
+    // The first letter stays and the space isn't blanked.
+    assert_eq!(cloze_jumble("ice cream", "We ate ice cream today.", &settings).unwrap(), "We ate i__ _____ today.");
+}
+
+#[test]
+fn phrase_list_csv_columns_can_be_missing() {
+    let list = PhraseList::parse("phrase,blanks\nthe cat sat,3\nbig dog\n", FileFormat::Csv).unwrap();
+    let entries = list.get_entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].phrase, "the cat sat");
+    assert_eq!(entries[0].blank_count, Some(3));
+    assert_eq!(entries[0].hint, None);
+    assert_eq!(entries[1].blank_count, None);
+    // Without a header row there's only the phrase.
+    let list = PhraseList::parse("red fox,A hint\n", FileFormat::Csv).unwrap();
+    assert_eq!(list.get_entries()[0].phrase, "red fox");
+    assert_eq!(list.get_entries()[0].hint, None);
+}
+
+#[test]
+fn phrase_list_csv_skips_comments_and_duplicates() {
+    let list = PhraseList::parse("# Animals\nphrase,category,hint\ncat,Pets,Says meow\n#fox,Wild\n,,\n  Cat  ,Pets,\n", FileFormat::Csv).unwrap();
+    assert_eq!(list.get_entries().len(), 1);
+    let entry = &list.get_entries()[0];
+    assert_eq!((entry.phrase.as_str(), entry.hint.as_deref(), entry.category.as_deref()), ("cat", Some("Says meow"), Some("Pets")));
+}
+
+#[test]
+fn phrase_list_bad_blank_count_is_an_error() {
+    let error = PhraseList::parse("phrase,blanks\ncat,2\ndog,two\n", FileFormat::Csv).unwrap_err();
+    assert_eq!(error, "Row 3: \"two\" is not a number of blanks.");
+    let error = PhraseList::parse("phrase,blanks\n# note\ncat,x", FileFormat::Csv).unwrap_err();
+    assert_eq!(error, "Row 3: \"x\" is not a number of blanks.");
+    assert!(PhraseList::parse("phrase,blanks\ncat,-1\n", FileFormat::Csv).is_err());
+    assert!(PhraseList::parse(r#"[{"phrase": "cat", "blanks": "two"}]"#, FileFormat::Json).is_err());
+}
+
+#[test]
+fn phrase_list_json() {
+    let list = PhraseList::parse(r#"["the cat sat", {"phrase": "big dog", "hint": "Woof", "blanks": 2}, "  "]"#, FileFormat::Json).unwrap();
+    let entries = list.get_entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].phrase, "the cat sat");
+    assert_eq!(entries[1].hint.as_deref(), Some("Woof"));
+    assert_eq!(entries[1].blank_count, Some(2));
+    assert!(PhraseList::parse(r#"{"phrase": "cat"}"#, FileFormat::Json).is_err());
+    assert!(PhraseList::parse(r#"[{"hint": "no phrase"}]"#, FileFormat::Json).is_err());
+    assert!(PhraseList::parse("[\"cat\"", FileFormat::Json).is_err());
+}
+
+#[test]
+fn scramble_mixes_up_each_word() {
+    let settings = Settings {
+        mode: Mode::Scramble,
+        ..Default::default()
+    };
+    let dictionary = &settings.dictionary;
+    for phrase in ["the cat sat on the mat", "don't stop, big dog!", "Paris in spring"] {
+        for seed in 0..20 {
+            random::set_seed(seed);
+            let mut puzzle = Puzzle::new(&[phrase], &settings).unwrap();
+            puzzle.build().unwrap();
+            let phrase = &puzzle.get_phrases()[0];
+            let jumble = puzzle::get_graphemes(phrase.get_jumble());
+            assert_ne!(phrase.get_jumble(), phrase.get_phrase());
+            assert_eq!(jumble.len(), phrase.get_graphemes().len());
+            // Spaces and punctuation stay where they were.
+            for (index, grapheme) in phrase.get_graphemes().iter().enumerate() {
+                if !puzzle::is_letter(grapheme) {
+                    assert_eq!(&jumble[index], grapheme);
+                }
+            }
+            // Every word that could be scrambled no longer spells a word.
+            for (start, end) in phrase.get_word_ranges() {
+                let word = &phrase.get_graphemes()[start..end];
+                if word.iter().map(|grapheme| grapheme.to_lowercase()).unique().count() > 1 {
+                    assert!(!dictionary.contains(&jumble[start..end].concat()), "{} in {}", jumble[start..end].concat(), phrase.get_jumble());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn scramble_needs_two_different_letters() {
+    let settings = Settings {
+        mode: Mode::Scramble,
+        ..Default::default()
+    };
+    assert!(Puzzle::new(&["a"], &settings).is_err());
+    assert!(Puzzle::new(&["zz"], &settings).is_err());
+    // Two different letters across the phrase isn't enough if no one word has them.
+    let mut puzzle = Puzzle::new(&["I, a zz!"], &settings).unwrap();
+    assert!(puzzle.build().is_err());
+    let dictionary = Dictionary::new(&[] as &[&str]);
+    assert_eq!(puzzle::scramble_word(&puzzle::get_graphemes("zz"), &dictionary).unwrap(), None);
+    assert_eq!(puzzle::scramble_word(&puzzle::get_graphemes("Aa"), &dictionary).unwrap(), None);
+}
+
+// Five of the other orders of "stop" are words. A scramble never lands on one of them, and a word
+// whose only other order is a word can't be scrambled at all.
+#[test]
+fn scramble_never_spells_a_dictionary_word() {
+    let dictionary = Dictionary::new(&["tops", "pots", "spot", "post", "opts"]);
+    for seed in 0..50 {
+        random::set_seed(seed);
+        let scrambled = puzzle::scramble_word(&puzzle::get_graphemes("stop"), &dictionary).unwrap().unwrap().concat();
+        assert!(scrambled != "stop" && !dictionary.contains(&scrambled), "{}", scrambled);
+    }
+    let ab = puzzle::get_graphemes("ab");
+    assert!(puzzle::scramble_word(&ab, &Dictionary::new(&["ba"])).is_err());
+    assert_eq!(puzzle::scramble_word(&ab, &Dictionary::new(&[] as &[&str])).unwrap(), Some(slice_str_to_strings(&["b", "a"])));
+}
+
+#[test]
+fn classic_circles_spell_the_answer() {
+    let riddle = Riddle::new("What did the baker say when the bread rose?", "Yeast of all!").unwrap();
+    for seed in 0..20 {
+        random::set_seed(seed);
+        let mut puzzle = Puzzle::new_classic(&riddle, &word_list::ALL_SECOND_GRADE, &Settings::default()).unwrap();
+        puzzle.build().unwrap();
+        assert_eq!(puzzle.get_phrases().len(), Settings::default().riddle_word_count);
+        let circled = puzzle.get_phrases().iter()
+            .flat_map(|phrase| phrase.get_circled_indexes().iter().map(move |index| phrase.get_graphemes()[*index].clone()))
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(circled, riddle.get_letters().into_iter().sorted().collect::<Vec<_>>());
+        for phrase in puzzle.get_phrases() {
+            assert!(!phrase.get_circled_indexes().is_empty());
+            assert!(phrase.get_circled_indexes().len() < phrase.get_graphemes().len());
+        }
+    }
+}
+
+#[test]
+fn classic_needs_enough_letters_and_words() {
+    let letters = Riddle::new("Why?", "So.").unwrap().get_letters();
+    assert_eq!(letters, vec!["s", "o"]);
+    // Two letters can't be spread over three words.
+    let error = riddle::choose_circled_words(&letters, &word_list::ALL_SECOND_GRADE, 3).unwrap_err();
+    assert!(error.starts_with("The answer has 2 letters"), "{}", error);
+    // Words that are too short, repeat one letter, or aren't all letters don't count.
+    let error = riddle::choose_circled_words(&letters, &["ox", "zzz", "it's", "toss", "Toss "], 2).unwrap_err();
+    assert!(error.starts_with("Need 2 words to choose from but the list has 1"), "{}", error);
+    assert!(riddle::choose_circled_words(&letters, &word_list::ALL_SECOND_GRADE, 0).is_err());
+    let riddle = Riddle::new("Why?", "So.").unwrap();
+    let settings = Settings {
+        riddle_word_count: 3,
+        ..Default::default()
+    };
+    assert!(Puzzle::new_classic(&riddle, &word_list::ALL_SECOND_GRADE, &settings).is_err());
+    assert!(Riddle::new("Why?", "?!").is_err());
+}
+
+fn sorted_lowercase(graphemes: &[String]) -> Vec<String> {
+    graphemes.iter().map(|grapheme| grapheme.to_lowercase()).sorted().collect()
+}
+
+#[test]
+fn scramble_word_keeps_graphemes_whole() {
+    let dictionary = Dictionary::new(&[] as &[&str]);
+    for word in ["café", "cafe\u{301}", "Fuß", "वाक्", "षट्क", "Éclair"] {
+        let graphemes = puzzle::get_graphemes(word);
+        for seed in 0..20 {
+            random::set_seed(seed);
+            let scrambled = puzzle::scramble_word(&graphemes, &dictionary).unwrap().unwrap();
+            assert_eq!(scrambled.len(), graphemes.len(), "{:?}", scrambled);
+            assert_ne!(scrambled, graphemes);
+            // Only the case of a letter can change, and only if it stays one grapheme.
+            if word != "Fuß" {
+                assert_eq!(sorted_lowercase(&scrambled), sorted_lowercase(&graphemes));
+            }
+            assert!(!scrambled.contains(&"SS".to_string()));
+        }
+    }
+}
+
+#[test]
+fn scramble_non_ascii_phrases() {
+    let settings = Settings {
+        mode: Mode::Scramble,
+        dictionary: Dictionary::new(&[] as &[&str]),
+        ..Default::default()
+    };
+    for phrase in ["वाक् षट्", "Fuß ab", "Crème brûlée!", "cre\u{300}me bru\u{302}le\u{301}e", "Ça, c'est Noël."] {
+        for seed in 0..20 {
+            random::set_seed(seed);
+            let mut puzzle = Puzzle::new(&[phrase], &settings).unwrap();
+            puzzle.build().unwrap();
+            let phrase = &puzzle.get_phrases()[0];
+            assert_eq!(phrase.get_graphemes().concat(), phrase.get_phrase());
+            let jumble = puzzle::get_graphemes(phrase.get_jumble());
+            let expected = phrase.get_graphemes();
+            if !phrase.get_phrase().contains('्') {
+                // Indic scripts can join graphemes differently once shuffled, so only the others can
+                // be compared grapheme by grapheme.
+                assert_eq!(jumble.len(), expected.len(), "{} became {}", phrase.get_phrase(), phrase.get_jumble());
+                for (index, grapheme) in expected.iter().enumerate() {
+                    if !puzzle::is_letter(grapheme) {
+                        assert_eq!(&jumble[index], grapheme);
+                    }
+                }
+                for (start, end) in phrase.get_word_ranges() {
+                    if expected[start..end].iter().all(|grapheme| grapheme != "ß") {
+                        assert_eq!(sorted_lowercase(&jumble[start..end]), sorted_lowercase(&expected[start..end]));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn blanks_keep_accents_and_case() {
+    for phrase in ["Café Olé", "Cafe\u{301} Ole\u{301}", "ÉCOLE été"] {
+        for seed in 0..20 {
+            random::set_seed(seed);
+            let settings = Settings {
+                blank_count: 4,
+                ..Default::default()
+            };
+            let mut puzzle = Puzzle::new(&[phrase], &settings).unwrap();
+            puzzle.build().unwrap();
+            let phrase = &puzzle.get_phrases()[0];
+            assert_eq!(phrase.get_graphemes().concat(), phrase.get_phrase());
+            let mut missing = phrase.get_blank_indexes().iter().map(|index| phrase.get_graphemes()[*index].clone()).collect::<Vec<_>>();
+            let mut missing_letters = phrase.get_missing_letters().clone();
+            missing.sort();
+            missing_letters.sort();
+            // The letters keep their accents and case, decomposed or not.
+            assert_eq!(missing_letters, missing);
+            assert_eq!(puzzle::get_graphemes(phrase.get_jumble()).len(), phrase.get_graphemes().len());
+            let answer = phrase.get_phrase().to_string();
+            assert_eq!(puzzle.check(&[&answer]).unwrap().get_score(), 1.0);
+        }
+    }
+    assert_eq!(puzzle::get_graphemes("e\u{301}t\u{e9}"), vec!["e\u{301}", "t", "\u{e9}"]);
+}
@@ -0,0 +1,66 @@
+use word_games::word_list::{lint, LintKind, LintSettings, Severity};
+
+fn get_kinds(words: &[&str], settings: &LintSettings) -> Vec<(Severity, LintKind, Vec<String>)> {
+    lint(words, settings).into_iter()
+        .map(|issue| (issue.severity, issue.kind, issue.words))
+        .collect()
+}
+
+fn settings() -> LintSettings {
+    LintSettings { length_max: 10, allow_spaces: false }
+}
+
+#[test]
+fn clean_list_has_no_issues() {
+    assert!(lint(&["apple", "grape", "melon"], &settings()).is_empty());
+}
+
+#[test]
+fn duplicates_ignore_case_and_whitespace() {
+    assert_eq!(get_kinds(&["Apple", " apple", "melon"], &settings()),
+        vec![(Severity::Error, LintKind::Duplicate, vec!["apple".to_string()])]);
+}
+
+#[test]
+fn non_letters_are_errors_unless_spaces_are_allowed() {
+    assert_eq!(get_kinds(&["ice-cream"], &settings()),
+        vec![(Severity::Error, LintKind::NonAlphabetic, vec!["ice-cream".to_string()])]);
+    assert_eq!(get_kinds(&["ice cream"], &settings()),
+        vec![(Severity::Error, LintKind::NonAlphabetic, vec!["ice cream".to_string()])]);
+    let settings = LintSettings { allow_spaces: true, ..settings() };
+    assert!(lint(&["ice cream"], &settings).is_empty());
+    assert_eq!(get_kinds(&["ice-cream"], &settings).len(), 1);
+}
+
+#[test]
+fn long_words_are_errors_not_counting_spaces() {
+    assert_eq!(get_kinds(&["abcdefghijk"], &settings()),
+        vec![(Severity::Error, LintKind::TooLong, vec!["abcdefghijk".to_string()])]);
+    let settings = LintSettings { allow_spaces: true, ..settings() };
+    assert!(lint(&["abcde fghij"], &settings).is_empty());
+}
+
+#[test]
+fn contained_words_are_warnings() {
+    assert_eq!(get_kinds(&["cat", "catalog"], &settings()),
+        vec![(Severity::Warning, LintKind::Contained, vec!["cat".to_string(), "catalog".to_string()])]);
+}
+
+#[test]
+fn reversed_words_are_warnings() {
+    assert_eq!(get_kinds(&["stop", "pots"], &settings()),
+        vec![(Severity::Warning, LintKind::ReversedCollision, vec!["pots".to_string(), "stop".to_string()])]);
+    assert_eq!(get_kinds(&["tab", "batch"], &settings()),
+        vec![(Severity::Warning, LintKind::ReversedCollision, vec!["tab".to_string(), "batch".to_string()])]);
+    // A palindrome inside another word is only reported as contained.
+    assert_eq!(get_kinds(&["pop", "popcorn"], &settings()),
+        vec![(Severity::Warning, LintKind::Contained, vec!["pop".to_string(), "popcorn".to_string()])]);
+}
+
+#[test]
+fn most_severe_issues_come_first() {
+    let severities = lint(&["tab", "batch", "cat", "catalog", "cat", "ice-cream"], &settings()).iter()
+        .map(|issue| issue.severity)
+        .collect::<Vec<_>>();
+    assert_eq!(severities, vec![Severity::Error, Severity::Error, Severity::Warning, Severity::Warning]);
+}
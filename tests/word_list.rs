@@ -0,0 +1,142 @@
+use word_games::random;
+use word_games::word_list::{self, FileFormat, LengthBand, PartOfSpeech, SampleSettings, WordList};
+
+fn parse(text: &str, format: FileFormat) -> WordList {
+    WordList::parse("test", text, format).unwrap()
+}
+
+#[test]
+fn text_skips_comments_and_blank_lines() {
+    let list = parse("# Animals\ncat\n\n  dog  # a pet\n#bird\n", FileFormat::Text);
+    assert_eq!(list.get_words(), vec!["cat", "dog"]);
+}
+
+#[test]
+fn duplicates_are_dropped_ignoring_case() {
+    let list = parse("cat\nCat\ndog\n", FileFormat::Text);
+    assert_eq!(list.get_words(), vec!["cat", "dog"]);
+    assert_eq!(list.get_removed_duplicates(), &vec!["Cat".to_string()]);
+}
+
+#[test]
+fn csv_without_headers_uses_the_first_column() {
+    let list = parse("cat,extra\ndog\n", FileFormat::Csv);
+    assert_eq!(list.get_words(), vec!["cat", "dog"]);
+}
+
+#[test]
+fn csv_skips_comments_and_empty_rows() {
+    let list = parse("# Animals\nword\ncat\n\n# not a word\n,\ndog\n", FileFormat::Csv);
+    assert_eq!(list.get_words(), vec!["cat", "dog"]);
+}
+
+#[test]
+fn csv_with_no_rows_is_empty() {
+    assert!(parse("", FileFormat::Csv).is_empty());
+    assert!(parse("word\n", FileFormat::Csv).is_empty());
+}
+
+#[test]
+fn json_can_be_an_array_or_named() {
+    let list = parse(r#"["cat", {"word": "dog"}]"#, FileFormat::Json);
+    assert_eq!(list.get_words(), vec!["cat", "dog"]);
+    let list = parse(r#"{"name": "Pets", "words": ["cat"]}"#, FileFormat::Json);
+    assert_eq!(list.get_name(), "Pets");
+    assert_eq!(list.get_words(), vec!["cat"]);
+}
+
+#[test]
+fn bad_json_is_an_error() {
+    for text in ["", "[\"cat\"", "{\"words\": 3}", "[1, 2]", "{\"name\": \"Pets\"}"] {
+        assert!(WordList::parse("test", text, FileFormat::Json).is_err(), "{:?} should be an error", text);
+    }
+}
+
+#[test]
+fn unknown_extension_is_an_error() {
+    assert!(FileFormat::from_path("words.xlsx").is_err());
+    assert!(WordList::read_file("words.xlsx").is_err());
+}
+
+#[test]
+fn csv_header_columns_can_be_missing_or_reordered() {
+    let list = parse("word,tags,grade\ncat,pet; animal,2\ndog\n", FileFormat::Csv);
+    let cat = list.get_entry("cat").unwrap();
+    assert_eq!(cat.grade, Some(2));
+    assert_eq!(cat.tags, vec!["pet", "animal"]);
+    assert_eq!(cat.definition, None);
+    let dog = list.get_entry("dog").unwrap();
+    assert_eq!(dog.grade, None);
+    assert!(dog.tags.is_empty());
+}
+
+#[test]
+fn csv_bad_metadata_is_an_error() {
+    assert!(WordList::parse("test", "word,grade\ncat,second\n", FileFormat::Csv).is_err());
+    assert!(WordList::parse("test", "word,part_of_speech\ncat,thing\n", FileFormat::Csv).is_err());
+    // The row number is the line in the file, counting comments and blank lines.
+    let error = WordList::parse("test", "word,grade\n# note\n\ncat,second\n", FileFormat::Csv).unwrap_err();
+    assert!(error.starts_with("Row 4: "), "{}", error);
+}
+
+#[test]
+fn json_entries_keep_their_metadata() {
+    let list = parse(r#"[{"word": "run", "grade": 1, "part_of_speech": "verb", "example": "We run."}]"#, FileFormat::Json);
+    let entry = list.get_entry("run").unwrap();
+    assert_eq!(entry.grade, Some(1));
+    assert_eq!(entry.part_of_speech, Some(PartOfSpeech::Verb));
+    assert_eq!(entry.example.as_deref(), Some("We run."));
+    assert!(WordList::parse("test", r#"[{"word": "run", "grade": "one"}]"#, FileFormat::Json).is_err());
+}
+
+#[test]
+fn metadata_survives_a_round_trip() {
+    let list = parse("word,grade,definition,tags\ncat,2,\"A small pet, often furry\",pet;animal\n", FileFormat::Csv);
+    for format in [FileFormat::Csv, FileFormat::Json] {
+        let copy = parse(&list.to_format_string(format), format);
+        assert_eq!(copy.get_entries(), list.get_entries());
+    }
+}
+
+#[test]
+fn random_sublist_is_an_error_when_asking_for_too_many_words() {
+    let sublist = word_list::make_random_sublist(2, &word_list::WORDS_4).unwrap();
+    assert_eq!(sublist.len(), 2);
+    assert!(sublist.iter().all(|word| word_list::WORDS_4.contains(&word.as_str())));
+    assert!(word_list::make_random_sublist(4, &word_list::WORDS_4).is_err());
+}
+
+#[test]
+fn sample_respects_grade_and_length_bands() {
+    let list = parse("word,grade\ncat,1\nhorse,1\nelephant,1\ndog,2\n", FileFormat::Csv);
+    let settings = SampleSettings { grade: Some(1), length_bands: vec![LengthBand::new(6, 10, 1)], ..Default::default() };
+    let sublist = settings.sample(&list, 2).unwrap();
+    assert!(sublist.contains(&"elephant".to_string()));
+    assert!(!sublist.contains(&"dog".to_string()));
+    assert!(settings.sample(&list, 4).is_err());
+}
+
+#[test]
+fn sample_leaves_out_excluded_words_ignoring_case() {
+    let list = parse("cat\ndog\ncow\npig\n", FileFormat::Text);
+    let settings = SampleSettings { exclude: vec!["Dog".to_string(), " cow ".to_string()], ..Default::default() };
+    assert_eq!(settings.sample(&list, 2).unwrap(), vec!["cat", "pig"]);
+    assert!(settings.sample(&list, 3).is_err());
+}
+
+#[test]
+fn sample_words_share_letters_with_an_earlier_word() {
+    let list = parse("cat\nact\ntab\ndog\nzip\n", FileFormat::Text);
+    let settings = SampleSettings { shared_letters_min: 2, ..Default::default() };
+    for seed in 0..20 {
+        random::set_seed(seed);
+        assert_eq!(settings.sample(&list, 3).unwrap(), vec!["act", "cat", "tab"]);
+    }
+    // No two of these words have a letter in common.
+    let list = parse("cat\ndog\nzip\n", FileFormat::Text);
+    let settings = SampleSettings { shared_letters_min: 1, try_count_max: 5, ..Default::default() };
+    let error = settings.sample(&list, 2).unwrap_err();
+    assert!(error.contains("after 5 tries"), "{}", error);
+    let settings = SampleSettings { shared_letters_min: 0, ..settings };
+    assert_eq!(settings.sample(&list, 3).unwrap(), vec!["cat", "dog", "zip"]);
+}
@@ -0,0 +1,309 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use proptest::prelude::*;
+use std::collections::BTreeMap;
+use word_games::*;
+use word_games::grid::Position;
+use word_games::word_search::{CandidateSearch, ExcelStyle, Puzzle, Ranking, Settings};
+use word_games::word_search::play::Play;
+
+const GOLDEN_DIR: &str = "tests/golden";
+
+fn settings_strategy() -> impl Strategy<Value = Settings> {
+    (
+        prop::sample::subsequence(grid::DIRECTIONS.to_vec(), 1..=8),
+        prop_oneof![Just(CandidateSearch::FullScan), Just(CandidateSearch::IntersectionFirst)],
+        prop_oneof![Just(Ranking::Compact), Just(Ranking::Intersections)],
+        0.0f32..=1.0,
+        any::<bool>(),
+        any::<bool>(),
+    ).prop_map(|(directions, candidate_search, ranking, expansion, forbid_contained_words, prevent_same_axis_overlap)| {
+        Settings {
+            directions,
+            candidate_search,
+            ranking,
+            expansion,
+            forbid_contained_words,
+            prevent_same_axis_overlap,
+            try_count_max: 1,
+            ..Default::default()
+        }
+    })
+}
+
+// A small alphabet so that words have letters in common and cross each other often.
+fn words_strategy() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec("[a-f]{1,10}", 1..15)
+}
+
+fn check_puzzle(puzzle: &Puzzle, settings: &Settings) -> Result<(), TestCaseError> {
+    let grid = puzzle.get_grid();
+    let placements = puzzle.get_placements();
+    prop_assert_eq!(placements.len(), puzzle.get_words().len());
+
+    let mut expected_word_counts: BTreeMap<Position, usize> = BTreeMap::new();
+    let mut axis_words: BTreeMap<(Position, usize), &String> = BTreeMap::new();
+    for (word, placement) in placements.iter() {
+        let positions = placement.get_positions(word.len());
+        prop_assert_eq!(positions.len(), word.len());
+        let read_back = positions.iter().map(|position| puzzle.get_char(position)).collect::<String>();
+        prop_assert_eq!(&read_back, word, "placement {}", placement);
+        for position in positions {
+            prop_assert!(puzzle.get_bounds().contains(&position), "{} is outside {}", position, puzzle.get_bounds());
+            if settings.prevent_same_axis_overlap {
+                let other = axis_words.insert((position.clone(), placement.get_direction().get_axis()), word);
+                prop_assert!(other.is_none(), "\"{}\" runs along the same line as \"{}\" through {}", word, other.cloned().unwrap_or_default(), position);
+            }
+            *expected_word_counts.entry(position).or_insert(0) += 1;
+        }
+    }
+    for (position, cell) in grid.iter() {
+        let expected = expected_word_counts.get(&position).cloned().unwrap_or(0);
+        prop_assert_eq!(cell.get_word_count(), expected, "word count at {}", position);
+    }
+    Ok(())
+}
+
+// With forbid_contained_words it's an error if the words can't be placed and filled in so that
+// each is in only one place, which is common with a small alphabet.
+fn find_filled_puzzle(words: &[String], settings: &Settings) -> Option<Puzzle> {
+    let mut puzzle = match Puzzle::find_best_puzzle(words, settings) {
+        Ok(puzzle) => puzzle,
+        Err(_) if settings.forbid_contained_words => return None,
+        Err(e) => panic!("{}", e),
+    };
+    match puzzle.random_fill_optional() {
+        Ok(()) => Some(puzzle),
+        Err(_) if settings.forbid_contained_words => None,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn placements_read_back_their_words(words in words_strategy(), settings in settings_strategy(), seed in any::<u64>()) {
+        random::set_seed(seed);
+        let puzzle = match Puzzle::find_best_puzzle(&words, &settings) {
+            Ok(puzzle) => puzzle,
+            Err(_) if settings.forbid_contained_words => return Ok(()),
+            Err(e) => panic!("{}", e),
+        };
+        check_puzzle(&puzzle, &settings)?;
+    }
+
+    #[test]
+    fn random_fill_keeps_placed_words(words in words_strategy(), settings in settings_strategy(), seed in any::<u64>()) {
+        random::set_seed(seed);
+        let puzzle = match find_filled_puzzle(&words, &settings) {
+            Some(puzzle) => puzzle,
+            None => return Ok(()),
+        };
+        check_puzzle(&puzzle, &settings)?;
+        let bounds = puzzle.get_bounds();
+        for y in bounds.get_y_min()..=bounds.get_y_max() {
+            for x in bounds.get_x_min()..=bounds.get_x_max() {
+                prop_assert!(puzzle.get_char_xy(x, y).is_ascii_lowercase());
+            }
+        }
+    }
+
+    #[test]
+    fn every_word_is_found(words in words_strategy(), settings in settings_strategy(), seed in any::<u64>()) {
+        random::set_seed(seed);
+        let puzzle = match find_filled_puzzle(&words, &settings) {
+            Some(puzzle) => puzzle,
+            None => return Ok(()),
+        };
+        for word in puzzle.get_words() {
+            prop_assert!(puzzle.count_occurrences(word) >= 1, "\"{}\" isn't in the grid", word);
+        }
+        if settings.forbid_contained_words {
+            prop_assert!(puzzle.find_ambiguous_words().is_empty());
+        }
+    }
+}
+
+// Once the first word fills the bounds, a full scan that only starts inside them has nowhere to
+// put the next word.
+#[test]
+fn full_bounds_still_place_words() {
+    let settings = Settings {
+        candidate_search: CandidateSearch::FullScan,
+        directions: vec![word_search::Direction::NW],
+        ..Default::default()
+    };
+    random::set_seed(0);
+    let puzzle = Puzzle::find_best_puzzle(&slice_str_to_strings(&["a", "b"]), &settings).unwrap();
+    assert_eq!(puzzle.get_placements().len(), 2);
+}
+
+// Compare against a file in tests/golden. Run with UPDATE_GOLDEN=1 to rewrite the files after a
+// change that's meant to alter the output.
+fn check_golden(name: &str, actual: &str) {
+    let path = format!("{}/{}", GOLDEN_DIR, name);
+    if std::env::var("UPDATE_GOLDEN").is_ok() {
+        std::fs::create_dir_all(GOLDEN_DIR).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Unable to read \"{}\": {}", path, e));
+    assert!(expected == actual, "Output doesn't match \"{}\".\n\nExpected:\n{}\nActual:\n{}", path, expected, actual);
+}
+
+fn golden_puzzle(words: &[&str], settings: &Settings) -> Puzzle {
+    random::set_seed(1);
+    Puzzle::find_best_puzzle(&slice_str_to_strings(words), settings).unwrap()
+}
+
+#[test]
+fn golden_words_1() {
+    let mut puzzle = golden_puzzle(&word_list::WORDS_1, &Settings::default());
+    let answer = puzzle.get_puzzle_string();
+    puzzle.random_fill_optional().unwrap();
+    let s = format!("{}\n{}\n{}", answer, puzzle.get_puzzle_string(), puzzle.get_word_bank_string());
+    check_golden("words_1.txt", &s);
+}
+
+#[test]
+fn golden_second_grade_intersections() {
+    let settings = Settings {
+        ranking: Ranking::Intersections,
+        forbid_contained_words: true,
+        try_count_max: 3,
+        ..Default::default()
+    };
+    // "sum" is inside "amusing" backward and "living" is inside "nonliving", which is an error with
+    // forbid_contained_words.
+    let words = word_list::ALL_SECOND_GRADE.iter()
+        .filter(|word| !["sum", "living"].contains(word))
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut puzzle = golden_puzzle(&words, &settings);
+    let answer = puzzle.get_puzzle_string();
+    let density = puzzle.get_excel_string(ExcelStyle::Density).unwrap();
+    check_golden("all_second_grade_intersections.txt", &format!("{}\n{}", answer, density));
+}
+
+// Short words over a few letters cross often, and random letters easily spell another copy. None
+// is inside another forward or backward, so each must be in exactly one place or it's an error.
+// With a single try per puzzle, about half of the seeds can't manage that.
+#[test]
+fn forbid_contained_words_never_returns_ambiguous_puzzles() {
+    let words = slice_str_to_strings(&["tan", "nap", "pat", "apt", "spa"]);
+    let settings = Settings {
+        forbid_contained_words: true,
+        try_count_max: 1,
+        ..Default::default()
+    };
+    let mut filled_count = 0;
+    for seed in 0..50 {
+        random::set_seed(seed);
+        if let Some(puzzle) = find_filled_puzzle(&words, &settings) {
+            for word in words.iter() {
+                assert_eq!(puzzle.count_occurrences(word), 1, "\"{}\" with seed {}:\n{}", word, seed, puzzle.get_puzzle_string());
+            }
+            filled_count += 1;
+        }
+    }
+    assert!(filled_count > 0);
+}
+
+#[test]
+fn forbid_contained_words_rejects_words_inside_other_words() {
+    let settings = Settings {
+        forbid_contained_words: true,
+        ..Default::default()
+    };
+    for words in [["sum", "summary"], ["summary", "mus"]].iter() {
+        let result = Puzzle::find_best_puzzle(&slice_str_to_strings(words), &settings);
+        assert!(result.is_err(), "{:?}", words);
+    }
+    let settings = Settings {
+        forbid_contained_words: false,
+        ..settings
+    };
+    assert!(Puzzle::find_best_puzzle(&slice_str_to_strings(&["sum", "summary"]), &settings).is_ok());
+}
+
+fn new_play(words: &[&str]) -> Play {
+    random::set_seed(0);
+    let puzzle = Puzzle::find_best_puzzle(&slice_str_to_strings(words), &Default::default()).unwrap();
+    Play::new(puzzle).unwrap()
+}
+
+fn press(play: &mut Play, code: KeyCode) -> bool {
+    play.handle_key(KeyEvent::from(code))
+}
+
+fn move_to(play: &mut Play, position: &Position) {
+    while play.get_cursor().get_x() < position.get_x() { press(play, KeyCode::Right); }
+    while play.get_cursor().get_x() > position.get_x() { press(play, KeyCode::Left); }
+    while play.get_cursor().get_y() < position.get_y() { press(play, KeyCode::Down); }
+    while play.get_cursor().get_y() > position.get_y() { press(play, KeyCode::Up); }
+}
+
+fn select(play: &mut Play, start: &Position, end: &Position) {
+    move_to(play, start);
+    press(play, KeyCode::Enter);
+    move_to(play, end);
+    press(play, KeyCode::Enter);
+}
+
+fn select_word(play: &mut Play, word: &str) {
+    let positions = play.get_puzzle().get_word_positions(word);
+    select(play, &positions[0], positions.last().unwrap());
+}
+
+#[test]
+fn play_quits_on_q_and_ctrl_c() {
+    let mut play = new_play(&["cat", "dog"]);
+    assert!(press(&mut play, KeyCode::Char('c')));
+    assert!(press(&mut play, KeyCode::Esc));
+    assert!(!press(&mut play, KeyCode::Char('q')));
+    assert!(!play.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+}
+
+#[test]
+fn play_cursor_stops_at_the_bounds() {
+    let mut play = new_play(&["cat", "dog"]);
+    let bounds = play.get_puzzle().get_bounds();
+    let (top_left, bottom_right) = (Position::new(bounds.get_x_min(), bounds.get_y_min()), Position::new(bounds.get_x_max(), bounds.get_y_max()));
+    let step_count = bounds.get_size();
+    assert_eq!(play.get_cursor(), &top_left);
+    press(&mut play, KeyCode::Up);
+    press(&mut play, KeyCode::Left);
+    assert_eq!(play.get_cursor(), &top_left);
+    for _ in 0..step_count {
+        press(&mut play, KeyCode::Down);
+        press(&mut play, KeyCode::Right);
+    }
+    assert_eq!(play.get_cursor(), &bottom_right);
+}
+
+#[test]
+fn play_finds_each_word_once_and_stops_the_timer() {
+    let mut play = new_play(&["cat", "dog"]);
+    let cursor = play.get_cursor().clone();
+    select(&mut play, &cursor, &cursor);
+    assert_eq!(play.get_message(), "No word there.");
+    select_word(&mut play, "cat");
+    assert_eq!(play.get_message(), "Found \"cat\"!");
+    select_word(&mut play, "cat");
+    assert_eq!(play.get_message(), "\"cat\" was already found.");
+    assert!(play.get_finish_time().is_none());
+    select_word(&mut play, "dog");
+    assert!(play.get_message().starts_with("All 2 words found"), "{}", play.get_message());
+    assert!(play.get_finish_time().is_some());
+}
+
+// Two words can't run along the same line where they overlap, so "at" is never placed over the
+// "at" in "cat". The player still sees a copy there and should get credit for it.
+#[test]
+fn play_accepts_a_copy_of_a_word() {
+    let mut play = new_play(&["at", "cat"]);
+    let positions = play.get_puzzle().get_word_positions("cat");
+    assert_eq!(play.get_puzzle().find_word_at(&positions[2], &positions[1]), Some(&"at".to_string()));
+    select(&mut play, &positions[1], &positions[2]);
+    assert_eq!(play.get_message(), "Found \"at\"!");
+}